@@ -1,15 +1,16 @@
-#![feature(cold_path)]
-
+use atoi_simd::AtoiSimdError;
 use criterion::{Criterion, criterion_group, criterion_main};
 use rand::prelude::*;
-use simd_parse_int::{self, ParseResult};
+use simd_parse_int::{self, PaddedSlice, ParseResult};
 use std::hint::black_box;
 use std::hint::cold_path;
 use std::io::Write;
-use std::{iter, usize};
+use std::iter;
+use std::ops::Range;
 
 struct BenchCases {
     name: String,
+    zeros: Range<usize>,
     storage: Vec<u8>,
     lengths: Vec<usize>,
 }
@@ -38,9 +39,11 @@ static POWERS_OF_TEN: [usize; 20] = [
 ];
 
 impl<'a> BenchCases {
-    fn new(count: usize, digits: usize, zeros: usize) -> BenchCases {
+    // every case has `digits` digits after a number of leading zeros in the `zeros` range
+    fn new(count: usize, digits: usize, zeros: Range<usize>) -> BenchCases {
         let mut cases = BenchCases {
-            name: format!("count={},digits={},zeros={}", count, digits, zeros),
+            name: format!("count={},digits={},zeros={:?}", count, digits, zeros),
+            zeros: zeros.clone(),
             storage: Vec::new(),
             lengths: Vec::with_capacity(count),
         };
@@ -58,21 +61,24 @@ impl<'a> BenchCases {
 
         for _ in 0..count {
             let mut rng = rand::rng();
-            let zero_count: usize = if zeros == 0 {
+            let zero_count: usize = if zeros.is_empty() {
                 0
             } else {
-                rng.random_range(0..zeros)
+                rng.random_range(zeros.clone())
             };
             let value: usize = rng.random_range(min_value..max_value);
 
             let old_len = cases.storage.len();
-            cases.storage.extend(iter::repeat(0x30).take(zero_count));
+            cases.storage.extend(iter::repeat_n(0x30, zero_count));
             write!(&mut cases.storage, "{}", value).unwrap();
 
             cases.lengths.push(cases.storage.len() - old_len);
         }
 
-        return cases;
+        // readable slack after the last case, for parse_padded
+        cases.storage.extend([0; PaddedSlice::PADDING]);
+
+        cases
     }
 
     fn iter(&'a self) -> BenchCasesIterator<'a> {
@@ -82,6 +88,16 @@ impl<'a> BenchCases {
             length_index: 0,
         }
     }
+
+    fn iter_padded(&'a self) -> impl Iterator<Item = PaddedSlice<'a>> {
+        let mut storage_index = 0;
+
+        self.lengths.iter().map(move |&len| {
+            let case = PaddedSlice::new(&self.storage[storage_index..], len).unwrap();
+            storage_index += len;
+            case
+        })
+    }
 }
 
 struct BenchCasesIterator<'a> {
@@ -94,7 +110,7 @@ impl<'a> Iterator for BenchCasesIterator<'a> {
     type Item = &'a [u8];
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.storage_index == self.cases.storage.len() {
+        if self.length_index == self.cases.lengths.len() {
             return None;
         }
 
@@ -147,15 +163,132 @@ fn parse_naive(x: &[u8]) -> Result<(usize, usize), ()> {
     Ok((value, len))
 }
 
+// atoi_simd rejects numbers longer than 20 chars unless asked to skip leading zeros
+#[inline(always)]
+fn parse_atoi_simd(x: &[u8], zeros: usize) -> Result<usize, AtoiSimdError<'_>> {
+    if zeros == 0 {
+        atoi_simd::parse::<usize>(x)
+    } else {
+        atoi_simd::parse_skipped::<usize>(x)
+    }
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    let cases = BenchCases::new(1024, 20, 0);
+    bench_cases(c, &BenchCases::new(1024, 20, 0..0));
+
+    // zero-padded fixed-width fields, between 16 and 39 bytes wide
+    bench_cases(c, &BenchCases::new(1024, 16, 0..24));
+
+    // same, but always wider than 32 bytes (33 to 40) so every one of them takes the AVX2 path
+    bench_cases(c, &BenchCases::new(1024, 16, 17..25));
+
+    bench_delimited(c, 1024, 8);
+
+    bench_long_buffer(c, 1024, 8);
+}
+
+// `count` numbers with up to `digits` digits, each parsed from a slice that runs to the end
+// of a line of comma separated numbers: the slice length says nothing about the number length
+fn bench_long_buffer(c: &mut Criterion, count: usize, digits: usize) {
+    let name = format!("long_buffer,count={},digits={}", count, digits);
+    let mut rng = rand::rng();
+    let mut line = Vec::new();
+    let mut starts = Vec::with_capacity(count);
+    let mut expected_values = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let len = rng.random_range(1..=digits);
+        let value: usize = rng.random_range(0..POWERS_OF_TEN[len]);
+
+        starts.push(line.len());
+        expected_values.push(value);
+        write!(&mut line, "{},", value).unwrap();
+    }
+
+    for (&start, &value) in starts.iter().zip(&expected_values) {
+        if simd_parse_int::parse::<usize>(&line[start..]).value != value {
+            panic!("sanity check failed");
+        }
+    }
+
+    c.bench_function(format!("{}/simd_parse_int", name).as_str(), |b| {
+        b.iter(|| {
+            for &start in &starts {
+                black_box(simd_parse_int::parse::<usize>(&line[start..]));
+            }
+        })
+    });
+}
+
+// a line of `count` comma separated numbers with up to `digits` digits
+fn bench_delimited(c: &mut Criterion, count: usize, digits: usize) {
+    let name = format!("delimited,count={},digits={}", count, digits);
+    let mut rng = rand::rng();
+    let mut line = Vec::new();
+
+    for i in 0..count {
+        if i > 0 {
+            line.push(b',');
+        }
+
+        let len = rng.random_range(1..=digits);
+        let value: usize = rng.random_range(0..POWERS_OF_TEN[len]);
+        write!(&mut line, "{}", value).unwrap();
+    }
+
+    // calls parse for every number, skipping the delimiter after it
+    let parse_loop = |values: &mut Vec<usize>| {
+        let mut i = 0;
+
+        loop {
+            let result = simd_parse_int::parse::<usize>(&line[i..]);
+            values.push(result.value);
+            i += result.len + 1;
+
+            if i > line.len() {
+                break;
+            }
+        }
+    };
+
+    let mut values = Vec::with_capacity(count);
+    let mut delimited_values: Vec<usize> = Vec::with_capacity(count);
+    parse_loop(&mut values);
+    simd_parse_int::parse_delimited(&line, b',', &mut delimited_values).unwrap();
+
+    if values != delimited_values || values.len() != count {
+        panic!("sanity check failed");
+    }
+
+    c.bench_function(format!("{}/simd_parse_int", name).as_str(), |b| {
+        b.iter(|| {
+            values.clear();
+            parse_loop(&mut values);
+            black_box(&values);
+        })
+    });
+
+    c.bench_function(format!("{}/simd_parse_int_delimited", name).as_str(), |b| {
+        b.iter(|| {
+            values.clear();
+            simd_parse_int::parse_delimited(&line, b',', &mut values).unwrap();
+            black_box(&values);
+        })
+    });
+}
+
+fn bench_cases(c: &mut Criterion, cases: &BenchCases) {
+    for (case, padded) in cases.iter().zip(cases.iter_padded()) {
+        assert_eq!(
+            simd_parse_int::parse_padded::<usize>(padded),
+            simd_parse_int::parse::<usize>(case)
+        );
 
-    for case in cases.iter() {
         let ParseResult {
             value: parse_int_value,
             len,
-        } = simd_parse_int::parse(case);
-        let atoi_simd_value = atoi_simd::parse::<usize>(case).unwrap();
+        } = simd_parse_int::parse::<usize>(case);
+        let atoi_simd_value = parse_atoi_simd(case, cases.zeros.end).unwrap();
         let (naive_value, _) = parse_naive(case).unwrap();
 
         if len == 0 {
@@ -182,15 +315,26 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function(format!("{}/simd_parse_int", cases.name).as_str(), |b| {
         b.iter(|| {
             for case in cases.iter() {
-                black_box(simd_parse_int::parse(case));
+                black_box(simd_parse_int::parse::<usize>(case));
             }
         })
     });
 
+    c.bench_function(
+        format!("{}/simd_parse_int_padded", cases.name).as_str(),
+        |b| {
+            b.iter(|| {
+                for case in cases.iter_padded() {
+                    black_box(simd_parse_int::parse_padded::<usize>(case));
+                }
+            })
+        },
+    );
+
     c.bench_function(format!("{}/atoi_simd", cases.name).as_str(), |b| {
         b.iter(|| {
             for case in cases.iter() {
-                let _ = black_box(atoi_simd::parse::<usize>(case));
+                let _ = black_box(parse_atoi_simd(case, cases.zeros.end));
             }
         })
     });
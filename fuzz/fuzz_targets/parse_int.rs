@@ -26,7 +26,7 @@ fuzz_target!(|data: ValidNumber| {
     input.extend(data.suffix.iter());
     input[expected_len..].iter_mut().for_each(|c| if *c >= 0x30 && *c <= 0x39 { *c += 10 });
 
-    let ParseResult { value, len } = parse(&input[..]);
+    let ParseResult { value, len } = parse::<usize>(&input[..]);
 
     if value != data.number {
         println!("leading zeroes: {}, number: {}, suffix: {:?}", leading_zero_count, data.number, data.suffix);
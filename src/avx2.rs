@@ -0,0 +1,163 @@
+use crate::ParseResult;
+use crate::error::ParseError;
+use crate::hint::cold_path;
+use crate::r#impl::count_digits;
+use crate::r#impl::parse_16_chars;
+use crate::int::sealed::Unsigned;
+use crate::utils::shift_right_8x16;
+use core::arch::x86_64::__m128i;
+use core::arch::x86_64::__m256i;
+use core::arch::x86_64::_mm_cmpeq_epi8;
+use core::arch::x86_64::_mm_cvtsi128_si64;
+use core::arch::x86_64::_mm_loadu_si128;
+use core::arch::x86_64::_mm_movemask_epi8;
+use core::arch::x86_64::_mm_set1_epi8;
+use core::arch::x86_64::_mm256_add_epi8;
+use core::arch::x86_64::_mm256_castsi256_si128;
+use core::arch::x86_64::_mm256_cmpgt_epi8;
+use core::arch::x86_64::_mm256_extracti128_si256;
+use core::arch::x86_64::_mm256_loadu_si256;
+use core::arch::x86_64::_mm256_madd_epi16;
+use core::arch::x86_64::_mm256_maddubs_epi16;
+use core::arch::x86_64::_mm256_movemask_epi8;
+use core::arch::x86_64::_mm256_packus_epi32;
+use core::arch::x86_64::_mm256_set1_epi8;
+use core::arch::x86_64::_mm256_set1_epi16;
+use core::arch::x86_64::_mm256_set1_epi32;
+use core::arch::x86_64::_mm256_sub_epi8;
+
+/// Parses numbers with a lot of leading zeros 32 digits at a time, slices of up to 32 bytes
+/// are handed over to the SSE4.1 implementation.
+///
+/// Unlike the SSE4.1 implementation this only performs loads inside of the slice:
+/// a 32 byte load from a 16 byte aligned address could cross a page boundary.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub fn parse<T: Unsigned, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
+    if x.len() <= 32 {
+        return crate::r#impl::parse::<T, EXACT, OVERFLOW>(x);
+    }
+
+    let mut value = T::ZERO;
+    let mut i = 0;
+
+    // whole 32 byte chunks, stopping at the one that contains the end of the digits
+    while x.len() - i >= 32 {
+        let chunk = unsafe { _mm256_loadu_si256(x.as_ptr().add(i) as *const __m256i) };
+
+        let Some((high, low)) = parse_32_digits(chunk) else {
+            break;
+        };
+
+        i += 32;
+
+        let Some(new_value) = value
+            .mul_add_with::<OVERFLOW>(16, high)
+            .and_then(|value| value.mul_add_with::<OVERFLOW>(16, low))
+        else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<10>(&x[i..]),
+            });
+        };
+
+        value = new_value;
+    }
+
+    // we have less than 32 bytes left (or less than 32 digits), so we finish 16 bytes at a time
+    while i < x.len() {
+        let remaining = x.len() - i;
+
+        let chunk = if remaining >= 16 {
+            unsafe { _mm_loadu_si128(x.as_ptr().add(i) as *const __m128i) }
+        } else {
+            // load the last 16 bytes of the slice (x is longer than 32 bytes) and shift away
+            // the ones we already parsed, the zeros shifted in are not digits
+            let chunk = unsafe { _mm_loadu_si128(x.as_ptr().add(x.len() - 16) as *const __m128i) };
+            shift_right_8x16(chunk, 16 - remaining)
+        };
+
+        let result = parse_16_chars::<10>(chunk);
+        i += result.len;
+
+        if EXACT && result.len != remaining.min(16) {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        let Some(new_value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<10>(&x[i..]),
+            });
+        };
+
+        value = new_value;
+
+        if result.len != 16 {
+            break;
+        }
+    }
+
+    Ok(ParseResult { value, len: i })
+}
+
+/// whether the first 16 bytes of `x` are all '0', the only case where [`parse`] is faster
+/// than the SSE4.1 implementation: otherwise the digits fit in two 16 byte chunks anyway.
+/// `x` must be at least 16 bytes long.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn starts_with_zeros(x: &[u8]) -> bool {
+    debug_assert!(x.len() >= 16);
+
+    let chunk = unsafe { _mm_loadu_si128(x.as_ptr() as *const __m128i) };
+    let is_zero_bytemask = _mm_cmpeq_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+
+    _mm_movemask_epi8(is_zero_bytemask) == 0xffff
+}
+
+/// returns the values of the two groups of 16 digits in `input`, or `None` if
+/// it contains any non-digit
+#[inline]
+#[target_feature(enable = "avx2")]
+fn parse_32_digits(input: __m256i) -> Option<(u64, u64)> {
+    // same as parse_16_chars, see the comments there. The 256-bit instructions work
+    // on two independent 128-bit lanes, the first one holding the most significant digits
+    let ascii_zeros = _mm256_set1_epi8(0x30);
+    let mut chunk = _mm256_sub_epi8(input, ascii_zeros);
+
+    let nine_bytes = _mm256_set1_epi8(9);
+    let wrap = _mm256_set1_epi8(-128);
+    let is_non_digit_bytemask = _mm256_cmpgt_epi8(
+        _mm256_add_epi8(chunk, wrap),
+        _mm256_add_epi8(nine_bytes, wrap),
+    );
+
+    if _mm256_movemask_epi8(is_non_digit_bytemask) != 0 {
+        return None;
+    }
+
+    // pairs of digits, the first byte of each pair is multiplied by 10
+    let tens = _mm256_set1_epi16(1 << 8 | 10);
+    chunk = _mm256_maddubs_epi16(chunk, tens);
+
+    // groups of four digits
+    let hundreds = _mm256_set1_epi32(1 << 16 | 100);
+    chunk = _mm256_madd_epi16(chunk, hundreds);
+
+    // back to 16-bit parts, within each lane
+    chunk = _mm256_packus_epi32(chunk, chunk);
+
+    // groups of eight digits, each lane now has two of them in its low 64 bits
+    let ten_thousands = _mm256_set1_epi32(1 << 16 | 10000);
+    chunk = _mm256_madd_epi16(chunk, ten_thousands);
+
+    let high = _mm_cvtsi128_si64(_mm256_castsi256_si128(chunk)) as u64;
+    let low = _mm_cvtsi128_si64(_mm256_extracti128_si256::<1>(chunk)) as u64;
+
+    Some((
+        (high & 0xffffffff) * 100000000 + (high >> 32),
+        (low & 0xffffffff) * 100000000 + (low >> 32),
+    ))
+}
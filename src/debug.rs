@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use std::arch::x86_64::__m128i;
+use core::arch::x86_64::__m128i;
 
 #[cfg(debug_assertions)]
 pub fn print_chunk(chunk: __m128i) {
@@ -0,0 +1,67 @@
+use core::fmt;
+
+/// Reason why a parse failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input doesn't start with a digit (after the optional sign for signed types,
+    /// and the radix prefix for [`parse_prefixed`](crate::parse_prefixed)).
+    Empty,
+    /// The number doesn't fit in the requested type.
+    ///
+    /// `len` is the length of the whole number, including the sign, like
+    /// [`ParseResult::len`](crate::ParseResult::len) would have been, so that it can be skipped.
+    Overflow { len: usize },
+    /// The input contains something other than digits, reported by
+    /// [`try_parse_exact`](crate::try_parse_exact) instead of stopping at the first non-digit.
+    InvalidDigit,
+    /// The number has leading zeros (e.g. `007`), reported by
+    /// [`try_parse_canonical`](crate::try_parse_canonical).
+    LeadingZero,
+    /// The digits between separators are not in groups of three, reported by
+    /// [`try_parse_with_thousands_separator`](crate::try_parse_with_thousands_separator).
+    InvalidGrouping,
+    /// The number is zero, reported by [`try_parse_nonzero`](crate::try_parse_nonzero).
+    Zero,
+    /// The number is larger than the maximum passed to [`try_parse_bounded`](crate::try_parse_bounded)
+    /// or set with [`ParseOptions::max`](crate::ParseOptions::max).
+    ///
+    /// `len` is the length of the whole number, like for `Overflow`.
+    OutOfRange { len: usize },
+}
+
+impl ParseError {
+    /// adds the length of what comes before the digits (e.g. the sign)
+    /// to the length reported by `Overflow` and `OutOfRange`
+    pub(crate) fn with_prefix_len(self, prefix_len: usize) -> Self {
+        match self {
+            ParseError::Overflow { len } => ParseError::Overflow {
+                len: len + prefix_len,
+            },
+            ParseError::OutOfRange { len } => ParseError::OutOfRange {
+                len: len + prefix_len,
+            },
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "no digits to parse"),
+            ParseError::Overflow { len } => {
+                write!(f, "number too large to fit in target type ({len} bytes)")
+            }
+            ParseError::InvalidDigit => write!(f, "invalid digit found in input"),
+            ParseError::LeadingZero => write!(f, "number has leading zeros"),
+            ParseError::InvalidGrouping => write!(f, "digits are not in groups of three"),
+            ParseError::Zero => write!(f, "number would be zero for non-zero type"),
+            ParseError::OutOfRange { len } => {
+                write!(f, "number out of the allowed range ({len} bytes)")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,22 @@
+//! Branch hints. `cold_path` is stable, `likely` is only available on nightly with the
+//! `nightly` feature and otherwise copied from `core::hint`, which builds it on `cold_path`.
+
+pub(crate) use core::hint::cold_path;
+#[cfg(all(
+    feature = "nightly",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub(crate) use core::hint::likely;
+
+#[cfg(all(
+    not(feature = "nightly"),
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[inline(always)]
+pub(crate) fn likely(b: bool) -> bool {
+    if !b {
+        cold_path();
+    }
+
+    b
+}
@@ -1,118 +1,156 @@
+use crate::ParseResult;
+use crate::error::ParseError;
+use crate::hint::cold_path;
+use crate::hint::likely;
+use crate::int::CHECKED;
+use crate::int::Groups;
+use crate::int::POWERS_OF_TEN;
+use crate::int::ParseInt;
+use crate::int::WRAPPING;
+use crate::int::digit_value;
+use crate::int::sealed::Unsigned;
 use crate::utils::_mm_set2_epi8;
 use crate::utils::_mm_set2_epi16;
-use crate::utils::POWERS_OF_TEN;
 use crate::utils::shift_left_8x16;
 use crate::utils::shift_right_8x16;
-use std::arch::x86_64::__m128i;
-use std::arch::x86_64::_mm_add_epi8;
-use std::arch::x86_64::_mm_and_si128;
-use std::arch::x86_64::_mm_cmpgt_epi8;
-use std::arch::x86_64::_mm_cvtsi128_si64;
-use std::arch::x86_64::_mm_load_si128;
-use std::arch::x86_64::_mm_madd_epi16;
-use std::arch::x86_64::_mm_maddubs_epi16;
-use std::arch::x86_64::_mm_movemask_epi8;
-use std::arch::x86_64::_mm_packus_epi32;
-use std::arch::x86_64::_mm_set_epi8;
-use std::arch::x86_64::_mm_set_epi16;
-use std::arch::x86_64::_mm_set1_epi8;
-use std::arch::x86_64::_mm_sub_epi8;
-use std::hint::cold_path;
-use std::hint::likely;
-
-#[derive(PartialEq, Eq, Debug)]
-pub struct ParseResult {
-    pub value: usize,
-    pub len: usize,
-}
-
-impl From<ParseResult> for Option<usize> {
-    fn from(result: ParseResult) -> Self {
-        if result.len == 0 {
-            None
-        } else {
-            Some(result.value)
-        }
-    }
-}
+use alloc::vec::Vec;
+use core::arch::x86_64::__m128i;
+use core::arch::x86_64::_mm_add_epi8;
+use core::arch::x86_64::_mm_and_si128;
+use core::arch::x86_64::_mm_andnot_si128;
+use core::arch::x86_64::_mm_blendv_epi8;
+use core::arch::x86_64::_mm_cmpeq_epi8;
+use core::arch::x86_64::_mm_cmpgt_epi8;
+use core::arch::x86_64::_mm_cvtsi128_si64;
+use core::arch::x86_64::_mm_extract_epi16;
+use core::arch::x86_64::_mm_extract_epi32;
+use core::arch::x86_64::_mm_load_si128;
+use core::arch::x86_64::_mm_loadu_si128;
+use core::arch::x86_64::_mm_madd_epi16;
+use core::arch::x86_64::_mm_maddubs_epi16;
+use core::arch::x86_64::_mm_movemask_epi8;
+use core::arch::x86_64::_mm_or_si128;
+use core::arch::x86_64::_mm_packus_epi32;
+use core::arch::x86_64::_mm_set_epi8;
+use core::arch::x86_64::_mm_set_epi16;
+use core::arch::x86_64::_mm_set_epi64x;
+use core::arch::x86_64::_mm_set1_epi8;
+use core::arch::x86_64::_mm_shuffle_epi8;
+use core::arch::x86_64::_mm_srli_si128;
+use core::arch::x86_64::_mm_sub_epi8;
+use core::arch::x86_64::_mm_testz_si128;
 
-impl From<ParseResult> for Result<usize, ()> {
-    fn from(result: ParseResult) -> Self {
-        if result.len == 0 {
-            Err(())
-        } else {
-            Ok(result.value)
-        }
-    }
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse<T: Unsigned, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
+    parse_radix::<T, 10, EXACT, OVERFLOW>(x)
 }
 
+/// parses digits in base `RADIX`, which must be either 2, 4, 8, 10 or 16.
+/// When `EXACT` is set the whole slice must be digits, otherwise an `InvalidDigit` error
+/// is returned instead of stopping at the first non-digit. `OVERFLOW` is one of
+/// the constants in the int module
 #[inline]
 #[target_feature(enable = "sse4.1")]
-pub fn parse(x: &[u8]) -> ParseResult {
+pub fn parse_radix<T: Unsigned, const RADIX: u32, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
     if x.len() <= 6 {
-        let mut value = 0_usize;
+        let mut value = 0_u64;
         let mut len = 0;
 
         for (i, byte) in x.iter().enumerate() {
-            let c = byte.wrapping_sub(0x30);
-            if c > 9 {
+            let c = if RADIX == 10 {
+                byte.wrapping_sub(0x30)
+            } else {
+                digit_value(*byte)
+            };
+
+            if c as u32 >= RADIX {
                 break;
             }
 
-            value = value * 10 + c as usize;
+            value = value * RADIX as u64 + c as u64;
             len = i + 1;
         }
 
-        return ParseResult { value, len };
-    }
+        if EXACT && len != x.len() {
+            return Err(ParseError::InvalidDigit);
+        }
 
-    // realign str to 16 bytes by masking away the last 4 bits of its address
-    let mut str = x.as_ptr();
+        // narrow types (e.g. u8) can overflow even with so few digits
+        let Some(value) = T::from_u64_with::<OVERFLOW>(value) else {
+            cold_path();
+            return Err(ParseError::Overflow { len });
+        };
 
-    let offset = str.addr() & 0b1111_usize;
-    str = unsafe { str.sub(offset) };
+        return Ok(ParseResult { value, len });
+    }
 
-    // this load breaks pointer provenance given that we load from a memory location that comes before the start of the slice
-    // however there is no way to encode this safely and it's not UB (it's just a load from a pointer after all)
-    let mut chunk = unsafe { _mm_load_si128(str as *const __m128i) };
+    // realign the chunks to 16 bytes, see load_chunk
+    let offset = chunk_offset(x);
+    let mut chunk = load_chunk(x, offset, 0);
 
     // since we loaded some bytes before the start of the actual string here we shift them away
     chunk = shift_right_8x16(chunk, offset);
 
-    let mut result = parse_last_chars(chunk, x.len().min(16) as i8);
+    // narrow types can't hold more significant digits than T::MAX_DIGITS, so we only combine
+    // those (wrapping still needs the whole value)
+    let mut result = if RADIX == 10 && OVERFLOW != WRAPPING && T::MAX_DIGITS < 16 {
+        parse_last_chars_narrow(chunk, x.len().min(16) as i8, T::MAX_DIGITS)
+    } else {
+        parse_last_chars::<RADIX>(chunk, x.len().min(16) as i8)
+    };
     let mut i = result.len;
-    let mut value = result.value;
+
+    // in exact mode every byte up to the end of the slice (or of the chunk) must be a digit,
+    // i.e. the digit mask can't stop early
+    if EXACT && result.len != x.len().min(16 - offset) {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    let Some(mut value) = T::from_u64_with::<OVERFLOW>(result.value) else {
+        cold_path();
+        return Err(ParseError::Overflow {
+            len: i + count_digits::<RADIX>(&x[i..]),
+        });
+    };
 
     // string is not all digits
     if result.len != 16 - offset {
-        return ParseResult { value, len: i };
+        return Ok(ParseResult { value, len: i });
     }
 
     // this duplicates the "last round" code below to speed up the common case
     // of 16 < size <= 32 by trading off duplicated code for better branch prediction.
     if likely(x.len() - i <= 16) {
         let chunk_len = (x.len() - i) as i8;
-        chunk = unsafe { _mm_load_si128(str.add(16) as *const __m128i) };
+        chunk = load_chunk(x, offset, 1);
 
-        let result = parse_last_chars(chunk, chunk_len);
+        let result = parse_last_chars::<RADIX>(chunk, chunk_len);
         i += result.len;
 
+        if EXACT && i != x.len() {
+            return Err(ParseError::InvalidDigit);
+        }
+
         // from now on we need to check for overflow because (10^33 - 1) > 2^64
         // (maximum representable number > maximum number that fits in 64 bits)
-        let Some(new_value) = value.checked_mul(POWERS_OF_TEN[result.len]) else {
+        // and for hex even 16 digits can fill a u64
+        let Some(new_value) = value.mul_add_radix_with::<OVERFLOW>(RADIX, result.len, result.value)
+        else {
             cold_path();
-            return ParseResult { value: 0, len: 0 };
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<RADIX>(&x[i..]),
+            });
         };
 
-        let Some(new_value) = new_value.checked_add(result.value) else {
-            cold_path();
-            return ParseResult { value: 0, len: 0 };
-        };
-
-        return ParseResult {
+        return Ok(ParseResult {
             value: new_value,
             len: i,
-        };
+        });
     }
 
     // strings with length > 32. The only way to end up here with a valid number
@@ -123,26 +161,28 @@ pub fn parse(x: &[u8]) -> ParseResult {
     // same as above, with checked addition for overflow and without shifting away bytes
     // continues until there are <= 16 bytes to parse
     loop {
-        chunk = unsafe { _mm_load_si128(str.add(16 * loops) as *const __m128i) };
+        chunk = load_chunk(x, offset, loops);
 
-        let result = parse_16_chars(chunk);
+        let result = parse_chunk::<RADIX>(chunk);
         i += result.len;
         loops += 1;
 
-        let Some(new_value) = value.checked_mul(POWERS_OF_TEN[result.len]) else {
-            cold_path();
-            return ParseResult { value: 0, len: 0 };
-        };
+        if EXACT && result.len != 16 {
+            return Err(ParseError::InvalidDigit);
+        }
 
-        let Some(new_value) = new_value.checked_add(result.value) else {
+        let Some(new_value) = value.mul_add_radix_with::<OVERFLOW>(RADIX, result.len, result.value)
+        else {
             cold_path();
-            return ParseResult { value: 0, len: 0 };
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<RADIX>(&x[i..]),
+            });
         };
 
         value = new_value;
 
         if result.len != 16 {
-            return ParseResult { value, len: i };
+            return Ok(ParseResult { value, len: i });
         }
 
         if x.len() - i <= 16 {
@@ -153,33 +193,616 @@ pub fn parse(x: &[u8]) -> ParseResult {
     // last round: we parse the remaining < 16 bytes
 
     let chunk_len = (x.len() - i) as i8;
+    chunk = load_chunk(x, offset, loops);
 
-    // this again breaks pointer provenance because it loads after the end of the slice
-    // however, again, the use we make of it is not UB as far as I understand
-    chunk = unsafe { _mm_load_si128(str.add(16 * loops) as *const __m128i) };
-
-    result = parse_last_chars(chunk, chunk_len);
+    result = parse_last_chars::<RADIX>(chunk, chunk_len);
     i += result.len;
 
-    let Some(value) = value.checked_mul(POWERS_OF_TEN[result.len]) else {
+    if EXACT && i != x.len() {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    let Some(value) = value.mul_add_radix_with::<OVERFLOW>(RADIX, result.len, result.value) else {
         cold_path();
-        return ParseResult { value: 0, len: 0 };
+        return Err(ParseError::Overflow {
+            len: i + count_digits::<RADIX>(&x[i..]),
+        });
     };
 
-    let Some(value) = value.checked_add(result.value) else {
+    Ok(ParseResult { value, len: i })
+}
+
+/// same as parse, but when the number ends in the first chunk and has more than `max_digits`
+/// digits after its leading zeros an `OutOfRange` error is returned from the digit mask alone,
+/// without combining the digits
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse_bounded<T: Unsigned>(
+    x: &[u8],
+    max_digits: usize,
+) -> Result<ParseResult<T>, ParseError> {
+    // the short path in parse doesn't use SIMD at all
+    if x.len() > 6 {
+        // same first chunk as in parse
+        let offset = chunk_offset(x);
+        let chunk = shift_right_8x16(load_chunk(x, offset, 0), offset);
+
+        // NOTE: signed comparison again, see parse_16_chars
+        let ascii_zeros = _mm_set1_epi8(0x30);
+        let wrap = _mm_set1_epi8(-128);
+        let nine_bytes = _mm_set1_epi8(9);
+        let is_non_digit_bytemask = _mm_cmpgt_epi8(
+            _mm_add_epi8(_mm_sub_epi8(chunk, ascii_zeros), wrap),
+            _mm_add_epi8(nine_bytes, wrap),
+        );
+        let is_zero_bytemask = _mm_cmpeq_epi8(chunk, ascii_zeros);
+
+        let digit_count =
+            (_mm_movemask_epi8(is_non_digit_bytemask) | 0x10000).trailing_zeros() as usize;
+        let zero_count = (!_mm_movemask_epi8(is_zero_bytemask)).trailing_zeros() as usize;
+
+        // the digits can only continue after the chunk if they fill it, and bytes after
+        // the end of the slice don't count
+        let chunk_len = 16 - offset;
+        if digit_count < chunk_len || x.len() <= chunk_len {
+            let digit_count = digit_count.min(x.len());
+
+            if digit_count - zero_count.min(digit_count) > max_digits {
+                return Err(ParseError::OutOfRange { len: digit_count });
+            }
+        }
+    }
+
+    parse::<T, false, CHECKED>(x)
+}
+
+/// same as parse for the first `len` bytes of `x`, which are followed by at least 16 bytes
+/// of padding: every chunk can be loaded with a single unaligned load, without realigning
+/// it nor special casing short slices
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse_padded<T: Unsigned>(x: &[u8], len: usize) -> Result<ParseResult<T>, ParseError> {
+    debug_assert!(x.len() >= len + 16);
+
+    // every load starts at most at len, so it ends inside of the padding
+    let load = |i: usize| unsafe { _mm_loadu_si128(x.as_ptr().add(i) as *const __m128i) };
+
+    let result = parse_last_chars::<10>(load(0), len.min(16) as i8);
+    let mut i = result.len;
+
+    let Some(mut value) = T::from_u64(result.value) else {
         cold_path();
-        return ParseResult { value: 0, len: 0 };
+        return Err(ParseError::Overflow {
+            len: i + count_digits::<10>(&x[i..len]),
+        });
     };
 
-    ParseResult { value, len: i }
+    // string is not all digits
+    if result.len != 16 {
+        return Ok(ParseResult { value, len: i });
+    }
+
+    // same as the last round of the loop below, for the common case of 16 < size <= 32
+    if likely(len - i <= 16) {
+        let result = parse_last_chars::<10>(load(i), (len - i) as i8);
+        i += result.len;
+
+        let Some(value) = value.checked_mul_add(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<10>(&x[i..len]),
+            });
+        };
+
+        return Ok(ParseResult { value, len: i });
+    }
+
+    loop {
+        let result = parse_last_chars::<10>(load(i), (len - i).min(16) as i8);
+        i += result.len;
+
+        let Some(new_value) = value.checked_mul_add(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits::<10>(&x[i..len]),
+            });
+        };
+
+        value = new_value;
+
+        if result.len != 16 || i == len {
+            return Ok(ParseResult { value, len: i });
+        }
+    }
+}
+
+/// same as parse in exact mode for a number whose length is known at compile time,
+/// the first `sign_len` bytes (0 or 1) hold a sign and are skipped.
+/// Since every byte must be a digit there's no need to look for the end of the number:
+/// the chunks are checked all at once and never masked
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse_fixed<T: Unsigned, const N: usize>(
+    x: &[u8; N],
+    sign_len: usize,
+) -> Result<ParseResult<T>, ParseError> {
+    debug_assert!(sign_len <= 1 && sign_len <= N);
+
+    if N == sign_len {
+        return Ok(ParseResult {
+            value: T::ZERO,
+            len: 0,
+        });
+    }
+
+    // the first chunk holds the leading digits that don't fill a whole chunk,
+    // the following ones are always 16 digits long
+    let first_len = (N - 1) % 16 + 1;
+
+    let (chunk, sign_index, shift) = if N <= 16 {
+        // right aligned after zeros, which don't change the value
+        let mut buffer = [b'0'; 16];
+        buffer[16 - N..].copy_from_slice(x);
+
+        let chunk = unsafe { _mm_loadu_si128(buffer.as_ptr() as *const __m128i) };
+        (chunk, 16 - N, 0)
+    } else {
+        // the bytes after first_len belong to the next chunk: they are checked twice
+        // and shifted away below
+        let chunk = unsafe { _mm_loadu_si128(x.as_ptr() as *const __m128i) };
+        (chunk, 0, 16 - first_len)
+    };
+
+    // the sign is neither a digit nor part of the value
+    let indices = _mm_set_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+    let sign_mask = _mm_and_si128(
+        _mm_cmpeq_epi8(indices, _mm_set1_epi8(sign_index as i8)),
+        _mm_set1_epi8(-(sign_len as i8)),
+    );
+
+    let (values, non_digits) = digit_values(chunk);
+    let values = _mm_andnot_si128(sign_mask, values);
+    let mut non_digits = _mm_andnot_si128(sign_mask, non_digits);
+
+    let values = if shift != 0 {
+        shift_left_8x16(values, shift)
+    } else {
+        values
+    };
+
+    let value = combine_digits(values, first_len);
+
+    let Some(mut value) = T::from_u64(value) else {
+        cold_path();
+        return Err(overflow_error(&x[sign_len..]));
+    };
+
+    let mut i = first_len;
+
+    while i < N {
+        let chunk = unsafe { _mm_loadu_si128(x.as_ptr().add(i) as *const __m128i) };
+
+        let (values, chunk_non_digits) = digit_values(chunk);
+        non_digits = _mm_or_si128(non_digits, chunk_non_digits);
+
+        let Some(new_value) = value.checked_mul_add(16, combine_digits(values, 16)) else {
+            cold_path();
+            return Err(overflow_error(&x[sign_len..]));
+        };
+
+        value = new_value;
+        i += 16;
+    }
+
+    // a single check for all the chunks
+    if _mm_testz_si128(non_digits, non_digits) == 0 {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    Ok(ParseResult {
+        value,
+        len: N - sign_len,
+    })
+}
+
+/// the error of parse_fixed and parse_delimited once the value overflowed: non-digits can
+/// make it overflow too, and they take precedence
+#[cold]
+fn overflow_error(digits: &[u8]) -> ParseError {
+    if digits.iter().all(u8::is_ascii_digit) {
+        ParseError::Overflow { len: digits.len() }
+    } else {
+        ParseError::InvalidDigit
+    }
 }
 
+/// same as parse for u128, which combines the partial results of up to three chunks
+/// with 128-bit multiply-adds. The first two chunks have at most 32 digits, which always fit,
+/// so only the third one needs to be checked for overflow
 #[inline]
 #[target_feature(enable = "sse4.1")]
-fn parse_16_chars(input: __m128i) -> ParseResult {
+pub fn parse_u128(x: &[u8]) -> Result<ParseResult<u128>, ParseError> {
+    // the short path in parse doesn't use SIMD at all
+    if x.len() <= 6 {
+        return parse::<u128, false, CHECKED>(x);
+    }
+
+    // same chunks as in parse
+    let offset = chunk_offset(x);
+
+    let chunk = shift_right_8x16(load_chunk(x, offset, 0), offset);
+    let first = parse_last_chars::<10>(chunk, x.len().min(16) as i8);
+    let mut i = first.len;
+
+    if i != 16 - offset || i == x.len() {
+        return Ok(ParseResult {
+            value: first.value as u128,
+            len: i,
+        });
+    }
+
+    let chunk_len = (x.len() - i).min(16) as i8;
+    let chunk = load_chunk(x, offset, 1);
+    let second = parse_last_chars::<10>(chunk, chunk_len);
+    i += second.len;
+
+    // both factors fit in 64 bits, so this is a single widening multiply
+    let value = first.value as u128 * POWERS_OF_TEN[second.len] as u128 + second.value as u128;
+
+    if second.len != 16 || i == x.len() {
+        return Ok(ParseResult { value, len: i });
+    }
+
+    let chunk_len = (x.len() - i).min(16) as i8;
+    let chunk = load_chunk(x, offset, 2);
+    let third = parse_last_chars::<10>(chunk, chunk_len);
+    i += third.len;
+
+    // more than 48 digits, the only way to fit is with lots of leading zeros
+    // which parse skips one chunk at a time
+    if third.len == 16 && i != x.len() {
+        cold_path();
+        return parse::<u128, false, CHECKED>(x);
+    }
+
+    // up to 48 digits, so a 39 digit number can be just below or just above u128::MAX
+    let Some(value) = value.checked_mul_add(third.len, third.value) else {
+        cold_path();
+        return Err(ParseError::Overflow {
+            len: i + count_digits::<10>(&x[i..]),
+        });
+    };
+
+    Ok(ParseResult { value, len: i })
+}
+
+/// for each 8-bit mask of the bytes to keep, the shuffle indices that move them to the front
+/// (0x80 zeroes the remaining bytes)
+static COMPACT_LUT: [u64; 256] = {
+    let mut lut = [0; 256];
+    let mut mask = 0;
+
+    while mask < 256 {
+        let mut indices = 0x8080808080808080_u64;
+        let mut kept = 0;
+        let mut byte = 0;
+
+        while byte < 8 {
+            if mask >> byte & 1 != 0 {
+                indices &= !(0xff << (8 * kept));
+                indices |= (byte as u64) << (8 * kept);
+                kept += 1;
+            }
+
+            byte += 1;
+        }
+
+        lut[mask] = indices;
+        mask += 1;
+    }
+
+    lut
+};
+
+/// parses decimal digits with single `separator` bytes between them. The separators are
+/// removed from each chunk with a shuffle, after which the digits are combined by
+/// parse_16_chars like in parse. When `GROUPED` is set the digits between separators
+/// must be in groups of three. The returned length includes the separators
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse_with_separator<T: Unsigned, const GROUPED: bool>(
+    x: &[u8],
+    separator: u8,
+) -> Result<ParseResult<T>, ParseError> {
+    let mut value = T::ZERO;
+    let mut overflowed = false;
+    let mut groups = Groups::default();
+    let mut i = 0;
+
+    loop {
+        // unlike parse this only loads inside of the slice, copying the last < 16 bytes to
+        // a zeroed buffer: zero bytes are not digits so the number ends there
+        let chunk = if x.len() - i >= 16 {
+            unsafe { _mm_loadu_si128(x.as_ptr().add(i) as *const __m128i) }
+        } else {
+            let mut tail = [0_u8; 16];
+            tail[..x.len() - i].copy_from_slice(&x[i..]);
+            unsafe { _mm_loadu_si128(tail.as_ptr() as *const __m128i) }
+        };
+
+        // NOTE: signed comparison, see parse_16_chars
+        let ascii_zeros = _mm_set1_epi8(0x30);
+        let wrap = _mm_set1_epi8(-128);
+        let nine_bytes = _mm_set1_epi8(9);
+        let is_non_digit_bytemask = _mm_cmpgt_epi8(
+            _mm_add_epi8(_mm_sub_epi8(chunk, ascii_zeros), wrap),
+            _mm_add_epi8(nine_bytes, wrap),
+        );
+        let is_digit_bitmask = (!_mm_movemask_epi8(is_non_digit_bytemask) & 0xffff) as u32;
+
+        let separators = _mm_set1_epi8(separator as i8);
+        let is_separator_bitmask = _mm_movemask_epi8(_mm_cmpeq_epi8(chunk, separators)) as u32;
+
+        // separators are part of the number only when they're between two digits, the ones
+        // at the edges of the chunk are checked against the bytes around it
+        let previous_is_digit = i > 0 && x[i - 1].is_ascii_digit();
+        let next_is_digit = x.get(i + 16).is_some_and(u8::is_ascii_digit);
+        let is_after_digit_bitmask = is_digit_bitmask << 1 | previous_is_digit as u32;
+        let is_before_digit_bitmask = is_digit_bitmask >> 1 | (next_is_digit as u32) << 15;
+        let is_number_bitmask = is_digit_bitmask
+            | (is_separator_bitmask & is_after_digit_bitmask & is_before_digit_bitmask);
+
+        // bytes from the start of the chunk to the end of the number, and the digits among them
+        let len = (!is_number_bitmask).trailing_zeros() as usize;
+        let is_kept_bitmask = is_digit_bitmask & ((1 << len) - 1);
+
+        // move the digits to the front of the chunk one half at a time, then append the
+        // digits of the high half to the ones of the low half
+        let zeroes = 0x8080808080808080_u64 as i64;
+        let low_shuffle =
+            _mm_set_epi64x(zeroes, COMPACT_LUT[is_kept_bitmask as usize & 0xff] as i64);
+        let high_shuffle =
+            _mm_set_epi64x(zeroes, COMPACT_LUT[is_kept_bitmask as usize >> 8] as i64);
+        let low = _mm_shuffle_epi8(chunk, low_shuffle);
+        let high = _mm_shuffle_epi8(_mm_srli_si128::<8>(chunk), high_shuffle);
+        let low_digits = (is_kept_bitmask & 0xff).count_ones() as usize;
+        let compacted = _mm_or_si128(low, shift_left_8x16(high, low_digits));
+
+        let result = parse_16_chars::<10>(compacted);
+
+        if let Some(new_value) = value.checked_mul_add(result.len, result.value) {
+            value = new_value;
+        } else {
+            // we keep going to find the end of the number
+            cold_path();
+            overflowed = true;
+        }
+
+        if GROUPED {
+            // one group at a time, a chunk of a valid number has at most 4 separators
+            let mut is_group_end_bitmask = is_separator_bitmask & ((1 << len) - 1);
+            let mut start = 0;
+
+            while is_group_end_bitmask != 0 {
+                let end = is_group_end_bitmask.trailing_zeros();
+                groups.add((is_kept_bitmask & ((1 << end) - (1 << start))).count_ones() as usize);
+                groups.separator()?;
+
+                start = end + 1;
+                is_group_end_bitmask &= is_group_end_bitmask - 1;
+            }
+
+            groups.add((is_kept_bitmask >> start).count_ones() as usize);
+        }
+
+        i += len;
+
+        if len != 16 {
+            break;
+        }
+    }
+
+    if overflowed {
+        return Err(ParseError::Overflow { len: i });
+    }
+
+    if GROUPED {
+        groups.finish()?;
+    }
+
+    Ok(ParseResult { value, len: i })
+}
+
+/// parses every field of `x` between delimiters like parse in exact mode, appending the values
+/// to `out`. Each chunk is loaded once: the delimiters and non-digits of all its bytes are found
+/// with a single movemask each, and the fields that start and end inside of it are parsed
+/// straight from the loaded chunk
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub fn parse_delimited<T: ParseInt>(
+    x: &[u8],
+    delimiter: u8,
+    out: &mut Vec<T>,
+) -> Result<(), ParseError> {
+    // the pointer of an empty slice can't be realigned, it's a single empty field anyway
+    if x.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let delimiters = _mm_set1_epi8(delimiter as i8);
+    let indices = _mm_set_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+
+    // positions below are counted from the start of the first chunk, which is offset bytes
+    // before the start of x, see load_chunk
+    let offset = chunk_offset(x);
+    let end = offset + x.len();
+    let mut start = offset;
+    let mut index = 0;
+
+    while 16 * index < end {
+        let chunk_start = 16 * index;
+        let chunk = load_chunk(x, offset, index);
+        index += 1;
+
+        let (values, non_digits) = digit_values(chunk);
+        let non_digits = _mm_movemask_epi8(non_digits) as u32;
+
+        // every field ends at a delimiter or at the end of x, which can be right after the
+        // last byte of the chunk. Delimiters outside of x are masked away
+        let mut ends = _mm_movemask_epi8(_mm_cmpeq_epi8(chunk, delimiters)) as u32;
+
+        if chunk_start == 0 {
+            ends &= u32::MAX << offset;
+        }
+
+        if end - chunk_start <= 16 {
+            ends &= (1 << (end - chunk_start)) - 1;
+            ends |= 1 << (end - chunk_start);
+        }
+
+        while ends != 0 {
+            let field_end = chunk_start + ends.trailing_zeros() as usize;
+            ends &= ends - 1;
+
+            let value = if start >= chunk_start {
+                let (mut first, last) = (start - chunk_start, field_end - chunk_start);
+
+                let mut negative = false;
+
+                if T::SIGNED && first != last {
+                    match x[start - offset] {
+                        b'-' => {
+                            negative = true;
+                            first += 1;
+                        }
+                        b'+' => first += 1,
+                        _ => {}
+                    }
+                }
+
+                if first == last {
+                    return Err(ParseError::Empty);
+                }
+
+                let field_mask = (u32::MAX << first) & !(u32::MAX << last);
+                if non_digits & field_mask != 0 {
+                    return Err(ParseError::InvalidDigit);
+                }
+
+                // move the digits to the end of the chunk and zero the bytes before them,
+                // which leaves at most 16 digits that always fit in a u64
+                let shifted = shift_left_8x16(values, 16 - last);
+                let before_first =
+                    _mm_cmpgt_epi8(_mm_set1_epi8((first + 16 - last) as i8), indices);
+                let digits = _mm_andnot_si128(before_first, shifted);
+
+                let magnitude = T::Unsigned::from_u64(combine_digits(digits, 16));
+                let Some(value) =
+                    magnitude.and_then(|magnitude| T::from_magnitude(magnitude, negative))
+                else {
+                    cold_path();
+                    return Err(ParseError::Overflow {
+                        len: field_end - start,
+                    });
+                };
+
+                value
+            } else {
+                // fields that cross the start of the chunk are parsed on their own,
+                // there's at most one of them per chunk
+                let field = &x[start - offset..field_end - offset];
+                crate::int::parse_signed(field, |digits| {
+                    parse::<_, true, CHECKED>(digits).map_err(|error| match error {
+                        ParseError::Overflow { .. } => overflow_error(digits),
+                        error => error,
+                    })
+                })?
+                .value
+            };
+
+            out.push(value);
+            start = field_end + 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// returns the distance of `x` from the 16 byte boundary before it, where load_chunk
+/// loads the first chunk from. It's always 0 when only loading inside of the slice
+#[inline(always)]
+fn chunk_offset(x: &[u8]) -> usize {
+    if crate::IN_BOUNDS_LOADS {
+        0
+    } else {
+        x.as_ptr().addr() & 0b1111_usize
+    }
+}
+
+/// loads the `index`-th chunk of `x`, which starts `16 * index - offset` bytes into it.
+/// Bytes outside of the slice must be masked away (or ignored) by the caller
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn load_chunk(x: &[u8], offset: usize, index: usize) -> __m128i {
+    if crate::IN_BOUNDS_LOADS {
+        // offset is 0 and the chunks are loaded one after the other, so only the last
+        // one can go past the end of the slice, in which case it's copied to a zeroed buffer
+        let start = 16 * index;
+
+        if x.len() - start >= 16 {
+            return unsafe { _mm_loadu_si128(x.as_ptr().add(start) as *const __m128i) };
+        }
+
+        let mut tail = [0_u8; 16];
+        tail[..x.len() - start].copy_from_slice(&x[start..]);
+        return unsafe { _mm_loadu_si128(tail.as_ptr() as *const __m128i) };
+    }
+
+    // the chunks are 16 byte aligned, so unlike x they never cross a page boundary and
+    // the bytes before the start and after the end of x can be loaded safely.
+    // This breaks pointer provenance given that we load from memory locations outside of the slice,
+    // however there is no way to encode this safely and it's not UB (it's just a load from a pointer after all)
+    let chunk = x.as_ptr().wrapping_sub(offset).wrapping_add(16 * index);
+    unsafe { _mm_load_si128(chunk as *const __m128i) }
+}
+
+/// returns the number of leading digits in `x`, to report the length of numbers that
+/// overflow. Unlike parse this only loads inside of the slice
+#[cold]
+#[target_feature(enable = "sse4.1")]
+pub(crate) fn count_digits<const RADIX: u32>(x: &[u8]) -> usize {
+    let mut i = 0;
+
+    loop {
+        let chunk = if x.len() - i >= 16 {
+            unsafe { _mm_loadu_si128(x.as_ptr().add(i) as *const __m128i) }
+        } else {
+            let mut tail = [0_u8; 16];
+            tail[..x.len() - i].copy_from_slice(&x[i..]);
+            unsafe { _mm_loadu_si128(tail.as_ptr() as *const __m128i) }
+        };
+
+        // we only need the digit count, the value is thrown away
+        let digits = parse_chunk::<RADIX>(chunk).len;
+        i += digits;
+
+        if digits != 16 {
+            return i;
+        }
+    }
+}
+
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) fn parse_16_chars<const RADIX: u32>(input: __m128i) -> ParseResult<u64> {
     let mut chunk = input;
     // algorithm comes from: https://kholdstare.github.io/technical/2020/05/26/faster-integer-parsing.html
 
+    // the comments below describe RADIX == 10, but the same steps work for any RADIX <= 10
+    // by using powers of RADIX instead of powers of ten: all the intermediate results
+    // are smaller than the decimal ones
+    let radix = RADIX as i64;
+
     // subtract '0' from the chunk so that valid number characters are
     // translated into their value in bytes (i.e. '0' -> 0)
     let ascii_zeros = _mm_set1_epi8(0x30);
@@ -190,7 +813,7 @@ fn parse_16_chars(input: __m128i) -> ParseResult {
     // so we need to translate the value 0 into -128
     // NOTE: we OR the mask with 0x1000 to avoid digit_count being zero if all the chars
     // are in range
-    let nine_bytes = _mm_set1_epi8(9);
+    let nine_bytes = _mm_set1_epi8((radix - 1) as i8);
     let wrap = _mm_set1_epi8(-128);
     let is_digit_bytemask =
         _mm_cmpgt_epi8(_mm_add_epi8(chunk, wrap), _mm_add_epi8(nine_bytes, wrap));
@@ -204,12 +827,12 @@ fn parse_16_chars(input: __m128i) -> ParseResult {
 
     // for each group of two digits we multiply by either 1 or 10 and sum the result
     // e.g. 9 |_mm_set1_epi8comes 92 | 15
-    let tens = _mm_set2_epi8(1, 10);
+    let tens = _mm_set2_epi8(1, radix as i8);
     chunk = _mm_maddubs_epi16(chunk, tens);
 
     // again with each group of four digits
     // e.g. 92 | 15 becomes 0 | 9215 (still fits in a short)
-    let hundreds = _mm_set2_epi16(1, 100);
+    let hundreds = _mm_set2_epi16(1, radix.pow(2) as i16);
     chunk = _mm_madd_epi16(chunk, hundreds);
 
     // the last madd has left the results in 32-bit parts
@@ -219,7 +842,8 @@ fn parse_16_chars(input: __m128i) -> ParseResult {
 
     // again with groups of eight digits
     // let ten_thousands = _mm_set_epi16(10000, 1, 10000, 1, 0, 0, 0, 0);
-    let ten_thousands = _mm_set_epi16(0, 0, 0, 0, 1, 10000, 1, 10000);
+    let ten_thousand = radix.pow(4) as i16;
+    let ten_thousands = _mm_set_epi16(0, 0, 0, 0, 1, ten_thousand, 1, ten_thousand);
     chunk = _mm_madd_epi16(chunk, ten_thousands);
 
     // again with a single group of 16 digits (all fit in the low 64 bits of "chunk")
@@ -227,14 +851,164 @@ fn parse_16_chars(input: __m128i) -> ParseResult {
     let r_low = _mm_cvtsi128_si64(chunk) & 0xffffffff;
 
     ParseResult {
-        value: (r_high + (r_low * 100000000)) as usize,
+        value: (r_high + (r_low * radix.pow(8))) as u64,
+        len: digit_count,
+    }
+}
+
+/// translates a chunk of decimal chars into their values, also returns a mask of the
+/// chars that are not digits (whose values are garbage)
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn digit_values(chunk: __m128i) -> (__m128i, __m128i) {
+    // NOTE: signed comparison again, see parse_16_chars
+    let values = _mm_sub_epi8(chunk, _mm_set1_epi8(0x30));
+    let wrap = _mm_set1_epi8(-128);
+    let non_digits = _mm_cmpgt_epi8(
+        _mm_add_epi8(values, wrap),
+        _mm_add_epi8(_mm_set1_epi8(9), wrap),
+    );
+
+    (values, non_digits)
+}
+
+/// combines the digit values of a chunk into a number like parse_16_chars, `digits` is the
+/// number of digits at the end of the chunk (the ones before them must be zero): with a
+/// constant `digits` the steps for digits that aren't there fold away
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn combine_digits(values: __m128i, digits: usize) -> u64 {
+    let chunk = _mm_maddubs_epi16(values, _mm_set2_epi8(1, 10));
+
+    if digits <= 2 {
+        return _mm_extract_epi16::<7>(chunk) as u64;
+    }
+
+    let chunk = _mm_madd_epi16(chunk, _mm_set2_epi16(1, 100));
+
+    if digits <= 4 {
+        return _mm_extract_epi32::<3>(chunk) as u64;
+    }
+
+    let chunk = _mm_packus_epi32(chunk, chunk);
+    let chunk = _mm_madd_epi16(chunk, _mm_set_epi16(0, 0, 0, 0, 1, 10000, 1, 10000));
+
+    let r_high = _mm_cvtsi128_si64(chunk) >> 32;
+
+    if digits <= 8 {
+        return r_high as u64;
+    }
+
+    let r_low = _mm_cvtsi128_si64(chunk) & 0xffffffff;
+
+    (r_high + r_low * 100_000_000) as u64
+}
+
+/// same as parse_last_chars in base 10, but only combines the last `max_digits` digits:
+/// if any of the ones before them isn't zero the value doesn't fit and we return u64::MAX
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn parse_last_chars_narrow(chunk: __m128i, len: i8, max_digits: usize) -> ParseResult<u64> {
+    let indices = _mm_set_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+    let is_before_end_mask = _mm_cmpgt_epi8(_mm_set1_epi8(len), indices);
+
+    // the zeroed bytes after the end of the string aren't digits
+    let (values, non_digits) = digit_values(_mm_and_si128(chunk, is_before_end_mask));
+    let digit_count = (_mm_movemask_epi8(non_digits) | 0x10000).trailing_zeros() as usize;
+
+    // move the digits to the end of the chunk, the bytes before them become zero
+    let values = shift_left_8x16(values, 16 - digit_count);
+
+    let is_too_high_mask = _mm_cmpgt_epi8(_mm_set1_epi8((16 - max_digits) as i8), indices);
+
+    if _mm_testz_si128(values, is_too_high_mask) == 0 {
+        return ParseResult {
+            value: u64::MAX,
+            len: digit_count,
+        };
+    }
+
+    ParseResult {
+        value: combine_digits(values, max_digits),
+        len: digit_count,
+    }
+}
+
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn parse_chunk<const RADIX: u32>(chunk: __m128i) -> ParseResult<u64> {
+    if RADIX == 16 {
+        parse_16_hex_chars(chunk)
+    } else {
+        parse_16_chars::<RADIX>(chunk)
+    }
+}
+
+#[inline]
+#[target_feature(enable = "sse4.1")]
+fn parse_16_hex_chars(input: __m128i) -> ParseResult<u64> {
+    // same idea as parse_16_chars, with two ranges of valid chars
+
+    // '0' - '9' are translated to their value like in parse_16_chars
+    let ascii_zeros = _mm_set1_epi8(0x30);
+    let decimal_values = _mm_sub_epi8(input, ascii_zeros);
+
+    // 'a' - 'f' and 'A' - 'F' are translated to 0 - 5: setting the 0x20 bit turns
+    // uppercase letters into lowercase ones
+    // NOTE: chars like 0x10 - 0x19 also become digits after setting the 0x20 bit,
+    // that's why we compute the decimal values from the original input
+    let lowercase_bit = _mm_set1_epi8(0x20);
+    let ascii_a = _mm_set1_epi8(0x61);
+    let letter_offsets = _mm_sub_epi8(_mm_or_si128(input, lowercase_bit), ascii_a);
+
+    // NOTE: signed comparison again, see parse_16_chars
+    let wrap = _mm_set1_epi8(-128);
+    let nine_bytes = _mm_set1_epi8(9);
+    let five_bytes = _mm_set1_epi8(5);
+    let is_non_decimal_bytemask = _mm_cmpgt_epi8(
+        _mm_add_epi8(decimal_values, wrap),
+        _mm_add_epi8(nine_bytes, wrap),
+    );
+    let is_non_letter_bytemask = _mm_cmpgt_epi8(
+        _mm_add_epi8(letter_offsets, wrap),
+        _mm_add_epi8(five_bytes, wrap),
+    );
+
+    // pick the value of letters (offset + 10) where the char is a letter, and the decimal value
+    // everywhere else. Non-digit values don't matter because they get shifted away
+    let ten_bytes = _mm_set1_epi8(10);
+    let letter_values = _mm_add_epi8(letter_offsets, ten_bytes);
+    let mut chunk = _mm_blendv_epi8(letter_values, decimal_values, is_non_letter_bytemask);
+
+    let is_non_digit_bytemask = _mm_and_si128(is_non_decimal_bytemask, is_non_letter_bytemask);
+    let is_non_digit_bitmask = _mm_movemask_epi8(is_non_digit_bytemask) | 0x10000;
+
+    let digit_count = is_non_digit_bitmask.trailing_zeros() as usize;
+    let non_digit_count = 16_usize - digit_count;
+
+    chunk = shift_left_8x16(chunk, non_digit_count);
+
+    // for each pair of nibbles we multiply the first one by 16 and sum them into a byte
+    // (stored in a 16-bit part)
+    let sixteens = _mm_set2_epi8(1, 16);
+    chunk = _mm_maddubs_epi16(chunk, sixteens);
+
+    // the bytes are already the big endian representation of the number, so we gather
+    // them in reverse order into the low 64 bits
+    let reverse = _mm_set_epi8(
+        -128, -128, -128, -128, -128, -128, -128, -128, 0, 2, 4, 6, 8, 10, 12, 14,
+    );
+    chunk = _mm_shuffle_epi8(chunk, reverse);
+
+    ParseResult {
+        value: _mm_cvtsi128_si64(chunk) as u64,
         len: digit_count,
     }
 }
 
 #[inline]
 #[target_feature(enable = "sse4.1")]
-fn parse_last_chars(chunk: __m128i, len: i8) -> ParseResult {
+fn parse_last_chars<const RADIX: u32>(chunk: __m128i, len: i8) -> ParseResult<u64> {
     // we've loaded 16 bytes so we need to mask the ones after the end of the string
     let indices = _mm_set_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
     let len_mask = _mm_set1_epi8(len);
@@ -242,5 +1016,5 @@ fn parse_last_chars(chunk: __m128i, len: i8) -> ParseResult {
 
     let chunk = _mm_and_si128(chunk, is_before_end_mask);
 
-    parse_16_chars(chunk)
+    parse_chunk::<RADIX>(chunk)
 }
@@ -0,0 +1,505 @@
+use crate::ParseResult;
+use crate::error::ParseError;
+use sealed::Unsigned;
+
+pub(crate) static POWERS_OF_TEN: [u64; 20] = [
+    1,
+    10,
+    100,
+    1000,
+    10000,
+    100000,
+    1000000,
+    10000000,
+    100000000,
+    1000000000,
+    10000000000,
+    100000000000,
+    1000000000000,
+    10000000000000,
+    100000000000000,
+    1000000000000000,
+    10000000000000000,
+    100000000000000000,
+    1000000000000000000,
+    10000000000000000000,
+];
+
+// what the kernels do when the number doesn't fit, passed as a const parameter:
+// checked kernels stop with an error, the other ones keep parsing the whole digit run
+pub(crate) const CHECKED: u8 = 0;
+pub(crate) const SATURATING: u8 = 1;
+pub(crate) const WRAPPING: u8 = 2;
+
+/// Integer types that can be produced by [`parse`](crate::parse).
+///
+/// This trait is sealed: it is implemented for all the primitive integers
+/// and cannot be implemented outside of this crate.
+pub trait ParseInt: sealed::Sealed {}
+
+pub(crate) mod sealed {
+    use super::{SATURATING, WRAPPING};
+
+    pub trait Sealed: Copy + Ord + core::fmt::Debug {
+        const ZERO: Self;
+        const SIGNED: bool;
+
+        /// type the digits are accumulated into before applying the sign
+        type Unsigned: Unsigned;
+
+        /// `NonZero` version of `Self`, returned by `parse_nonzero`
+        type NonZero: Copy + Ord + core::fmt::Debug;
+
+        /// returns `None` if `self` is zero
+        fn to_nonzero(self) -> Option<Self::NonZero>;
+
+        /// applies the sign to a parsed magnitude, returning `None` if the result doesn't fit
+        fn from_magnitude(magnitude: Self::Unsigned, negative: bool) -> Option<Self>;
+
+        /// same as `from_magnitude`, but clamps the result to `MIN` or `MAX`
+        fn saturating_from_magnitude(magnitude: Self::Unsigned, negative: bool) -> Self;
+
+        /// same as `from_magnitude`, but wraps around the boundary of the type
+        fn wrapping_from_magnitude(magnitude: Self::Unsigned, negative: bool) -> Self;
+
+        /// number of digits in the decimal representation of `self`, 0 for negative numbers
+        fn decimal_digits(self) -> usize;
+
+        /// calls the `from_magnitude` variant for the `OVERFLOW` behaviour
+        #[inline(always)]
+        fn from_magnitude_with<const OVERFLOW: u8>(
+            magnitude: Self::Unsigned,
+            negative: bool,
+        ) -> Option<Self> {
+            match OVERFLOW {
+                SATURATING => Some(Self::saturating_from_magnitude(magnitude, negative)),
+                WRAPPING => Some(Self::wrapping_from_magnitude(magnitude, negative)),
+                _ => Self::from_magnitude(magnitude, negative),
+            }
+        }
+    }
+
+    pub trait Unsigned: Sealed<Unsigned = Self> {
+        const MAX: Self;
+
+        /// number of digits in the decimal representation of `Self::MAX`
+        const MAX_DIGITS: usize;
+
+        fn from_u64(value: u64) -> Option<Self>;
+
+        /// truncates `value` to the width of `Self`
+        fn wrapping_from_u64(value: u64) -> Self;
+
+        /// computes `self * 10^digits + value`, returning `None` on overflow
+        fn checked_mul_add(self, digits: usize, value: u64) -> Option<Self>;
+
+        /// computes `self * radix^digits + value`, returning `None` on overflow
+        fn checked_mul_add_radix(self, radix: u32, digits: usize, value: u64) -> Option<Self>;
+
+        /// computes `self * radix^digits + value` modulo `2^BITS`
+        fn wrapping_mul_add_radix(self, radix: u32, digits: usize, value: u64) -> Self;
+
+        /// calls the `from_u64` variant for the `OVERFLOW` behaviour
+        #[inline(always)]
+        fn from_u64_with<const OVERFLOW: u8>(value: u64) -> Option<Self> {
+            match OVERFLOW {
+                SATURATING => Some(Self::from_u64(value).unwrap_or(Self::MAX)),
+                WRAPPING => Some(Self::wrapping_from_u64(value)),
+                _ => Self::from_u64(value),
+            }
+        }
+
+        /// same as `mul_add_radix_with` in base 10
+        #[inline(always)]
+        fn mul_add_with<const OVERFLOW: u8>(self, digits: usize, value: u64) -> Option<Self> {
+            self.mul_add_radix_with::<OVERFLOW>(10, digits, value)
+        }
+
+        /// calls the `checked_mul_add_radix` variant for the `OVERFLOW` behaviour
+        #[inline(always)]
+        fn mul_add_radix_with<const OVERFLOW: u8>(
+            self,
+            radix: u32,
+            digits: usize,
+            value: u64,
+        ) -> Option<Self> {
+            match OVERFLOW {
+                SATURATING => Some(
+                    self.checked_mul_add_radix(radix, digits, value)
+                        .unwrap_or(Self::MAX),
+                ),
+                WRAPPING => Some(self.wrapping_mul_add_radix(radix, digits, value)),
+                _ => self.checked_mul_add_radix(radix, digits, value),
+            }
+        }
+    }
+}
+
+/// returns the value of an ASCII digit in any radix up to 36 (case insensitive),
+/// or `u8::MAX` if `byte` is not a digit
+#[inline(always)]
+pub(crate) fn digit_value(byte: u8) -> u8 {
+    match byte {
+        b'0'..=b'9' => byte - b'0',
+        b'a'..=b'z' => byte - b'a' + 10,
+        b'A'..=b'Z' => byte - b'A' + 10,
+        _ => u8::MAX,
+    }
+}
+
+#[inline(always)]
+#[track_caller]
+pub(crate) fn assert_radix(radix: u32) {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be between 2 and 36, got {radix}"
+    );
+}
+
+#[inline(always)]
+#[track_caller]
+pub(crate) fn assert_not_digit(byte: u8, what: &str) {
+    assert!(
+        !byte.is_ascii_digit(),
+        "{what} can't be a digit, got {:?}",
+        byte as char
+    );
+}
+
+/// strips an optional leading sign when `T` is signed and parses the remaining digits
+/// with `parse_digits`. The returned length includes the sign byte.
+#[inline(always)]
+pub(crate) fn parse_signed<T: ParseInt>(
+    x: &[u8],
+    parse_digits: impl FnOnce(&[u8]) -> Result<ParseResult<T::Unsigned>, ParseError>,
+) -> Result<ParseResult<T>, ParseError> {
+    parse_signed_with::<T, CHECKED>(x, parse_digits)
+}
+
+/// same as [`parse_signed`], applying the sign with the `OVERFLOW` behaviour
+#[inline(always)]
+pub(crate) fn parse_signed_with<T: ParseInt, const OVERFLOW: u8>(
+    x: &[u8],
+    parse_digits: impl FnOnce(&[u8]) -> Result<ParseResult<T::Unsigned>, ParseError>,
+) -> Result<ParseResult<T>, ParseError> {
+    let (negative, sign_len) = match x.first() {
+        Some(b'-') if T::SIGNED => (true, 1),
+        Some(b'+') if T::SIGNED => (false, 1),
+        _ => (false, 0),
+    };
+
+    let result = parse_digits(&x[sign_len..]).map_err(|error| error.with_prefix_len(sign_len))?;
+
+    if result.len == 0 {
+        return Err(ParseError::Empty);
+    }
+
+    let Some(value) = T::from_magnitude_with::<OVERFLOW>(result.value, negative) else {
+        return Err(ParseError::Overflow {
+            len: result.len + sign_len,
+        });
+    };
+
+    Ok(ParseResult {
+        value,
+        len: result.len + sign_len,
+    })
+}
+
+/// checks that the digits between separators are in groups of three, except for the first
+/// group which can have one to three digits
+#[derive(Default)]
+pub(crate) struct Groups {
+    len: usize,
+    separated: bool,
+}
+
+impl Groups {
+    #[inline(always)]
+    pub(crate) fn add(&mut self, digits: usize) {
+        self.len += digits;
+    }
+
+    #[inline(always)]
+    pub(crate) fn separator(&mut self) -> Result<(), ParseError> {
+        if self.len > 3 {
+            return Err(ParseError::InvalidGrouping);
+        }
+
+        self.finish()?;
+
+        self.len = 0;
+        self.separated = true;
+
+        Ok(())
+    }
+
+    /// numbers without separators are fine, whatever their length
+    #[inline(always)]
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        if self.separated && self.len != 3 {
+            return Err(ParseError::InvalidGrouping);
+        }
+
+        Ok(())
+    }
+}
+
+/// turns a successful parse into a non-zero one, zero is reported as a `Zero` error
+#[inline(always)]
+pub(crate) fn to_nonzero<T: ParseInt>(
+    result: Result<ParseResult<T>, ParseError>,
+) -> Result<ParseResult<T::NonZero>, ParseError> {
+    let result = result?;
+
+    let Some(value) = result.value.to_nonzero() else {
+        return Err(ParseError::Zero);
+    };
+
+    Ok(ParseResult {
+        value,
+        len: result.len,
+    })
+}
+
+/// returns a `LeadingZero` error if `x` starts with a zero followed by more digits
+/// in base `radix`, a lone zero is fine
+#[inline(always)]
+pub(crate) fn check_canonical(x: &[u8], radix: u32) -> Result<(), ParseError> {
+    match x {
+        [b'0', digit, ..] if (digit_value(*digit) as u32) < radix => Err(ParseError::LeadingZero),
+        _ => Ok(()),
+    }
+}
+
+/// same as [`parse_signed`], but picks the radix passed to `parse_digits_radix` from a `0x`,
+/// `0o` or `0b` prefix (case insensitive) after the sign, falling back to decimal when
+/// there's none. The returned length includes the prefix.
+#[inline(always)]
+pub(crate) fn parse_prefixed<T: ParseInt>(
+    x: &[u8],
+    parse_digits_radix: impl FnOnce(&[u8], u32) -> Result<ParseResult<T::Unsigned>, ParseError>,
+) -> Result<ParseResult<T>, ParseError> {
+    parse_signed::<T>(x, |digits| {
+        let radix = match digits {
+            [b'0', b'x' | b'X', ..] => 16,
+            [b'0', b'o' | b'O', ..] => 8,
+            [b'0', b'b' | b'B', ..] => 2,
+            _ => return parse_digits_radix(digits, 10),
+        };
+
+        let result =
+            parse_digits_radix(&digits[2..], radix).map_err(|error| error.with_prefix_len(2))?;
+
+        // a prefix alone is not a number
+        if result.len == 0 {
+            return Err(ParseError::Empty);
+        }
+
+        Ok(ParseResult {
+            value: result.value,
+            len: result.len + 2,
+        })
+    })
+}
+
+/// same as [`parse_signed`], but rejects numbers larger than `max` with an `OutOfRange`
+/// error. `parse_digits` also gets the number of digits of `max` (without leading zeros),
+/// so that it can reject longer numbers before combining their digits
+#[inline(always)]
+pub(crate) fn parse_bounded<T: ParseInt>(
+    x: &[u8],
+    max: T,
+    parse_digits: impl FnOnce(&[u8], usize) -> Result<ParseResult<T::Unsigned>, ParseError>,
+) -> Result<ParseResult<T>, ParseError> {
+    // negative numbers can only be out of range when max is negative too,
+    // and the comparison below takes care of that
+    let negative = T::SIGNED && x.first() == Some(&b'-');
+    let max_digits = if negative {
+        usize::MAX
+    } else {
+        max.decimal_digits()
+    };
+
+    let result = parse_signed::<T>(x, |digits| parse_digits(digits, max_digits)).map_err(
+        |error| match error {
+            // a positive number that doesn't fit in T is larger than max too
+            ParseError::Overflow { len } if !negative => ParseError::OutOfRange { len },
+            _ => error,
+        },
+    )?;
+
+    if result.value > max {
+        return Err(ParseError::OutOfRange { len: result.len });
+    }
+
+    Ok(result)
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ParseInt for $ty {}
+
+            impl sealed::Sealed for $ty {
+                const ZERO: Self = 0;
+                const SIGNED: bool = false;
+
+                type Unsigned = Self;
+                type NonZero = core::num::NonZero<Self>;
+
+                #[inline(always)]
+                fn to_nonzero(self) -> Option<Self::NonZero> {
+                    Self::NonZero::new(self)
+                }
+
+                #[inline(always)]
+                fn from_magnitude(magnitude: Self, _negative: bool) -> Option<Self> {
+                    Some(magnitude)
+                }
+
+                #[inline(always)]
+                fn saturating_from_magnitude(magnitude: Self, _negative: bool) -> Self {
+                    magnitude
+                }
+
+                #[inline(always)]
+                fn wrapping_from_magnitude(magnitude: Self, _negative: bool) -> Self {
+                    magnitude
+                }
+
+                #[inline(always)]
+                fn decimal_digits(self) -> usize {
+                    self.checked_ilog10()
+                        .map_or((self == 0) as usize, |log| log as usize + 1)
+                }
+            }
+
+            impl Unsigned for $ty {
+                const MAX: Self = Self::MAX;
+                const MAX_DIGITS: usize = Self::MAX.ilog10() as usize + 1;
+
+                #[inline(always)]
+                fn from_u64(value: u64) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+
+                #[inline(always)]
+                fn wrapping_from_u64(value: u64) -> Self {
+                    value as Self
+                }
+
+                #[inline(always)]
+                fn checked_mul_add(self, digits: usize, value: u64) -> Option<Self> {
+                    let value = Self::from_u64(value)?;
+
+                    if self == 0 {
+                        return Some(value);
+                    }
+
+                    // any non-zero value followed by MAX_DIGITS more digits can't fit,
+                    // so we bail out before even looking up the power of ten
+                    if digits >= Self::MAX_DIGITS {
+                        return None;
+                    }
+
+                    self.checked_mul(POWERS_OF_TEN[digits] as Self)?
+                        .checked_add(value)
+                }
+
+                #[inline(always)]
+                fn checked_mul_add_radix(self, radix: u32, digits: usize, value: u64) -> Option<Self> {
+                    if radix == 10 {
+                        return self.checked_mul_add(digits, value);
+                    }
+
+                    let value = Self::from_u64(value)?;
+
+                    if self == 0 {
+                        return Some(value);
+                    }
+
+                    self.checked_mul((radix as Self).checked_pow(digits as u32)?)?
+                        .checked_add(value)
+                }
+
+                #[inline(always)]
+                fn wrapping_mul_add_radix(self, radix: u32, digits: usize, value: u64) -> Self {
+                    self.wrapping_mul((radix as Self).wrapping_pow(digits as u32))
+                        .wrapping_add(value as Self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_signed {
+    ($($ty:ty => $unsigned:ty),* $(,)?) => {
+        $(
+            impl ParseInt for $ty {}
+
+            impl sealed::Sealed for $ty {
+                const ZERO: Self = 0;
+                const SIGNED: bool = true;
+
+                type Unsigned = $unsigned;
+                type NonZero = core::num::NonZero<Self>;
+
+                #[inline(always)]
+                fn to_nonzero(self) -> Option<Self::NonZero> {
+                    Self::NonZero::new(self)
+                }
+
+                #[inline(always)]
+                fn from_magnitude(magnitude: $unsigned, negative: bool) -> Option<Self> {
+                    if !negative {
+                        return Self::try_from(magnitude).ok();
+                    }
+
+                    // the magnitude of MIN is one more than MAX, so we negate in the unsigned
+                    // domain to be able to represent it
+                    if magnitude > Self::MIN.unsigned_abs() {
+                        return None;
+                    }
+
+                    Some(magnitude.wrapping_neg() as Self)
+                }
+
+                #[inline(always)]
+                fn saturating_from_magnitude(magnitude: $unsigned, negative: bool) -> Self {
+                    match Self::from_magnitude(magnitude, negative) {
+                        Some(value) => value,
+                        None if negative => Self::MIN,
+                        None => Self::MAX,
+                    }
+                }
+
+                #[inline(always)]
+                fn wrapping_from_magnitude(magnitude: $unsigned, negative: bool) -> Self {
+                    if negative {
+                        magnitude.wrapping_neg() as Self
+                    } else {
+                        magnitude as Self
+                    }
+                }
+
+                #[inline(always)]
+                fn decimal_digits(self) -> usize {
+                    self.checked_ilog10()
+                        .map_or((self == 0) as usize, |log| log as usize + 1)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl_signed! {
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+}
@@ -1,15 +1,568 @@
-#![feature(likely_unlikely)]
-#![feature(cold_path)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    all(
+        feature = "nightly",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ),
+    feature(likely_unlikely)
+)]
 
+extern crate alloc;
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod error;
+mod hint;
+#[cfg(target_arch = "x86_64")]
 mod r#impl;
+mod int;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+mod options;
+mod padded;
+mod portable;
+mod result;
+#[cfg(target_arch = "x86_64")]
 mod utils;
 
-#[cfg(debug_assertions)]
+#[cfg(all(target_arch = "x86_64", debug_assertions, feature = "std"))]
 mod debug;
 
-pub use crate::r#impl::ParseResult;
+pub use crate::error::ParseError;
+pub use crate::int::ParseInt;
+pub use crate::options::ParseOptions;
+pub use crate::padded::PaddedSlice;
+pub use crate::result::ParseResult;
+
+use crate::int::CHECKED;
+use crate::int::SATURATING;
+use crate::int::WRAPPING;
+use crate::int::sealed::Unsigned;
+use alloc::vec::Vec;
+
+/// Parses the leading decimal digits of `x` into any primitive integer type.
+///
+/// Signed types accept an optional leading `+` or `-`, which is included in the returned `len`.
+/// On overflow (e.g. `b"256"` for a `u8`) or when `x` doesn't start with a digit
+/// the returned [`ParseResult`] has `len == 0`, use [`try_parse`] to tell the two apart.
+pub fn parse<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    try_parse(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse`], but reports why the parse failed.
+pub fn try_parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_signed(x, parse_digits::<_, false, CHECKED>)
+}
+
+/// Parses the whole of `x` as a decimal number into any primitive integer type.
+///
+/// Unlike [`parse`] this doesn't stop at the first non-digit: the result has `len == 0`
+/// unless `x` is made only of digits (after the optional sign of signed types) and the
+/// number fits in `T`, in which case `len == x.len()`.
+pub fn parse_exact<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    try_parse_exact(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_exact`], but reports why the parse failed.
+///
+/// A non-digit anywhere in `x` is reported as [`ParseError::InvalidDigit`], an empty `x`
+/// (or a lone sign) as [`ParseError::Empty`]. When `x` has both a non-digit and more digits
+/// than `T` can hold either error can be reported.
+pub fn try_parse_exact<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_signed(x, parse_digits::<_, true, CHECKED>)
+}
+
+/// Same as [`parse_exact`], for fixed-width fields whose length is known at compile time.
+///
+/// Knowing the length lets the SIMD implementations check that all the bytes are digits
+/// at once, and skip the steps that combine digits beyond `N`. Errors on non-digits take
+/// precedence over [`ParseError::Overflow`].
+pub fn parse_fixed<T: ParseInt, const N: usize>(x: &[u8; N]) -> ParseResult<T> {
+    try_parse_fixed(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_fixed`], but reports why the parse failed.
+pub fn try_parse_fixed<T: ParseInt, const N: usize>(
+    x: &[u8; N],
+) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_signed(x, |digits| parse_digits_fixed(x, N - digits.len()))
+}
+
+/// Same as [`parse`], but numbers that don't fit in `T` are clamped to `T::MAX`
+/// (or `T::MIN` for negative numbers) instead of failing.
+///
+/// The returned `len` always covers the whole digit run, so `len == 0` only when `x`
+/// doesn't start with a digit.
+pub fn parse_saturating<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    crate::int::parse_signed_with::<T, SATURATING>(x, parse_digits::<_, false, SATURATING>)
+        .unwrap_or(ParseResult {
+            value: T::ZERO,
+            len: 0,
+        })
+}
+
+/// Same as [`parse`], but numbers that don't fit in `T` wrap around its boundary instead
+/// of failing, like [`u64::wrapping_mul`] and [`u64::wrapping_add`] do.
+///
+/// The returned `len` always covers the whole digit run, so `len == 0` only when `x`
+/// doesn't start with a digit.
+pub fn parse_wrapping<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    crate::int::parse_signed_with::<T, WRAPPING>(x, parse_digits::<_, false, WRAPPING>).unwrap_or(
+        ParseResult {
+            value: T::ZERO,
+            len: 0,
+        },
+    )
+}
+
+/// Same as [`parse`], for a slice followed by padding.
+///
+/// The SIMD implementations can then load the input with plain unaligned loads, instead of
+/// realigning them to avoid reading past the end of a page.
+pub fn parse_padded<T: ParseInt>(x: PaddedSlice<'_>) -> ParseResult<T> {
+    try_parse_padded(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_padded`], but reports why the parse failed.
+pub fn try_parse_padded<T: ParseInt>(x: PaddedSlice<'_>) -> Result<ParseResult<T>, ParseError> {
+    let slice = x.as_slice();
+
+    crate::int::parse_signed(slice, |digits| {
+        // digits are the end of the slice, after the sign
+        let start = slice.len() - digits.len();
+        parse_digits_padded(&x.with_padding()[start..], digits.len())
+    })
+}
+
+/// Parses the leading decimal digits of `x` into any primitive integer type, rejecting
+/// numbers with leading zeros like JSON does.
+///
+/// Works like [`parse`], except that the result also has `len == 0` when `x` starts with a zero
+/// followed by more digits (e.g. `b"007"` or `b"-01"`). A lone zero (e.g. `b"0"` or `b"0,"`)
+/// is accepted.
+pub fn parse_canonical<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    try_parse_canonical(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_canonical`], but reports why the parse failed.
+///
+/// Leading zeros are reported as [`ParseError::LeadingZero`].
+pub fn try_parse_canonical<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_signed(x, |digits| {
+        crate::int::check_canonical(digits, 10)?;
+        parse_digits::<_, false, CHECKED>(digits)
+    })
+}
+
+/// Parses the leading decimal digits of `x` into the `NonZero` version of any primitive
+/// integer type (e.g. `NonZero<u64>` for `u64`).
+///
+/// Works like [`parse`], but returns `None` when the parse fails or the number is zero,
+/// whatever the number of zeros (e.g. `b"0000"` is rejected, while `b"0001"` is accepted).
+pub fn parse_nonzero<T: ParseInt>(x: &[u8]) -> Option<ParseResult<T::NonZero>> {
+    try_parse_nonzero::<T>(x).ok()
+}
+
+/// Same as [`parse_nonzero`], but reports why the parse failed.
+///
+/// Zero is reported as [`ParseError::Zero`].
+pub fn try_parse_nonzero<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T::NonZero>, ParseError> {
+    crate::int::to_nonzero(try_parse::<T>(x))
+}
+
+/// Parses the leading decimal digits of `x` into any primitive integer type, rejecting
+/// numbers larger than `max`.
+///
+/// Works like [`parse`], except that the result also has `len == 0` when the number is
+/// larger than `max` (e.g. `b"65536"` for a port with `max == 65535`). Numbers with more
+/// digits than `max` are rejected without computing their value.
+pub fn parse_bounded<T: ParseInt>(x: &[u8], max: T) -> ParseResult<T> {
+    try_parse_bounded(x, max).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_bounded`], but reports why the parse failed.
+///
+/// Numbers larger than `max` are reported as [`ParseError::OutOfRange`], even when they
+/// don't fit in `T` either.
+pub fn try_parse_bounded<T: ParseInt>(x: &[u8], max: T) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_bounded(x, max, parse_digits_bounded)
+}
+
+/// Parses the leading decimal digits of `x` into a `u128`.
+///
+/// Same as [`parse::<u128>`](parse), but with a kernel specialized for numbers wider
+/// than 64 bits (e.g. 128-bit ids or totals in nanoseconds): the digits are combined with
+/// 128-bit multiply-adds and only checked for overflow once, instead of after each chunk.
+pub fn parse_u128(x: &[u8]) -> ParseResult<u128> {
+    try_parse_u128(x).unwrap_or(ParseResult { value: 0, len: 0 })
+}
+
+/// Same as [`parse_u128`], but reports why the parse failed.
+pub fn try_parse_u128(x: &[u8]) -> Result<ParseResult<u128>, ParseError> {
+    crate::int::parse_signed(x, parse_digits_u128)
+}
+
+/// Parses the leading hexadecimal digits (`0-9`, `a-f`, `A-F`) of `x` into any primitive integer type.
+///
+/// Works like [`parse`]: signed types accept an optional sign, and the result has `len == 0`
+/// on overflow or when `x` doesn't start with a digit. A `0x` prefix is not accepted,
+/// use [`parse_prefixed`] for that.
+pub fn parse_hex<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    try_parse_hex(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_hex`], but reports why the parse failed.
+pub fn try_parse_hex<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_signed(x, |digits| parse_digits_radix::<_, false>(digits, 16))
+}
+
+/// Parses the leading digits of `x` in base `radix` into any primitive integer type.
+///
+/// Digits above 9 are the letters `a-z` (or `A-Z`), like in [`u64::from_str_radix`].
+/// Works like [`parse`]: signed types accept an optional sign, and the result has `len == 0`
+/// on overflow or when `x` doesn't start with a digit.
+///
+/// Radix 10 and 16 use the same code as [`parse`] and [`parse_hex`], radix 2, 4 and 8 use
+/// SIMD instructions too, while every other radix is parsed one digit at a time.
+///
+/// # Panics
+///
+/// Panics if `radix` is not between 2 and 36.
+#[track_caller]
+pub fn parse_radix<T: ParseInt>(x: &[u8], radix: u32) -> ParseResult<T> {
+    try_parse_radix(x, radix).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_radix`], but reports why the parse failed.
+///
+/// # Panics
+///
+/// Panics if `radix` is not between 2 and 36.
+#[track_caller]
+pub fn try_parse_radix<T: ParseInt>(x: &[u8], radix: u32) -> Result<ParseResult<T>, ParseError> {
+    crate::int::assert_radix(radix);
+
+    crate::int::parse_signed(x, |digits| parse_digits_radix::<_, false>(digits, radix))
+}
+
+/// Parses the leading digits of `x` into any primitive integer type, picking the radix
+/// from a `0x` (hexadecimal), `0o` (octal) or `0b` (binary) prefix, decimal otherwise.
+///
+/// The prefix letter can be either lowercase or uppercase, and goes after the optional sign
+/// of signed types (e.g. `-0x1f`). The returned `len` includes the sign and the prefix.
+/// Works like [`parse`]: the result has `len == 0` on overflow, when `x` doesn't start with
+/// a digit, or when a prefix is not followed by any digit of its radix.
+pub fn parse_prefixed<T: ParseInt>(x: &[u8]) -> ParseResult<T> {
+    try_parse_prefixed(x).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_prefixed`], but reports why the parse failed.
+///
+/// A prefix that is not followed by any digit (e.g. `b"0x"` or `b"0b2"`) is reported
+/// as [`ParseError::Empty`].
+pub fn try_parse_prefixed<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    crate::int::parse_prefixed(x, parse_digits_radix::<_, false>)
+}
+
+/// Parses the leading decimal digits of `x` into any primitive integer type, skipping the
+/// `separator` byte between them (e.g. `b"1_000_000"` or `b"1'000'000"`).
+///
+/// A separator is only skipped when it's between two digits: a separator at the start of the
+/// number, at its end or next to another separator ends it (e.g. `b"1__0"` parses as 1).
+/// Works like [`parse`] otherwise, and the returned `len` includes the separators.
+///
+/// # Panics
+///
+/// Panics if `separator` is a digit.
+#[track_caller]
+pub fn parse_with_separator<T: ParseInt>(x: &[u8], separator: u8) -> ParseResult<T> {
+    try_parse_with_separator(x, separator).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_with_separator`], but reports why the parse failed.
+///
+/// # Panics
+///
+/// Panics if `separator` is a digit.
+#[track_caller]
+pub fn try_parse_with_separator<T: ParseInt>(
+    x: &[u8],
+    separator: u8,
+) -> Result<ParseResult<T>, ParseError> {
+    crate::int::assert_not_digit(separator, "separator");
+
+    crate::int::parse_signed(x, |digits| {
+        parse_digits_with_separator::<_, false>(digits, separator)
+    })
+}
+
+/// Same as [`parse_with_separator`], but the separators must split the digits in groups of three
+/// (e.g. `b"1,000,000"` but not `b"10,00,000"`). Numbers without separators are accepted too.
+///
+/// # Panics
+///
+/// Panics if `separator` is a digit.
+#[track_caller]
+pub fn parse_with_thousands_separator<T: ParseInt>(x: &[u8], separator: u8) -> ParseResult<T> {
+    try_parse_with_thousands_separator(x, separator).unwrap_or(ParseResult {
+        value: T::ZERO,
+        len: 0,
+    })
+}
+
+/// Same as [`parse_with_thousands_separator`], but reports why the parse failed.
+///
+/// Misplaced separators are reported as [`ParseError::InvalidGrouping`].
+///
+/// # Panics
+///
+/// Panics if `separator` is a digit.
+#[track_caller]
+pub fn try_parse_with_thousands_separator<T: ParseInt>(
+    x: &[u8],
+    separator: u8,
+) -> Result<ParseResult<T>, ParseError> {
+    crate::int::assert_not_digit(separator, "separator");
+
+    crate::int::parse_signed(x, |digits| {
+        parse_digits_with_separator::<_, true>(digits, separator)
+    })
+}
+
+/// Parses every number of `x` separated by `delimiter` (e.g. `b"12,4553,8"`), appending their
+/// values to `out`.
+///
+/// Works like splitting `x` at every `delimiter` and calling [`parse_exact`] on each field, so
+/// empty fields (including the one after a trailing delimiter) are errors, except that like in
+/// [`parse_fixed`] errors on non-digits take precedence over [`ParseError::Overflow`]. The SIMD
+/// implementations find the delimiters of a whole chunk at once and parse all the short numbers
+/// in it without loading it again, instead of starting over for every number.
+///
+/// Stops at the first field that isn't a number and reports why, `out` then holds the values
+/// of the fields before it.
+///
+/// # Panics
+///
+/// Panics if `delimiter` is a digit.
+#[track_caller]
+pub fn parse_delimited<T: ParseInt>(
+    x: &[u8],
+    delimiter: u8,
+    out: &mut Vec<T>,
+) -> Result<(), ParseError> {
+    crate::int::assert_not_digit(delimiter, "delimiter");
+
+    parse_fields(x, delimiter, out)
+}
+
+/// parses the leading decimal digits of `x` with the best implementation for this CPU,
+/// or the whole of `x` when `EXACT` is set
+#[inline(always)]
+fn parse_digits<T: Unsigned, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        // AVX2 only helps with numbers that have lots of leading zeros. The slice length
+        // doesn't tell (the number can be followed by anything), so we look at the first
+        // chunk before paying for the extra check
+        return if x.len() > 32 && unsafe { crate::avx2::starts_with_zeros(x) } && has_avx2() {
+            unsafe { crate::avx2::parse::<_, EXACT, OVERFLOW>(x) }
+        } else {
+            unsafe { crate::r#impl::parse::<_, EXACT, OVERFLOW>(x) }
+        };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if has_neon() {
+        return unsafe { crate::neon::parse::<_, EXACT, OVERFLOW>(x) };
+    }
+
+    crate::portable::parse_digits::<_, EXACT, OVERFLOW>(x)
+}
+
+/// same as [`parse_digits`] for any radix, `radix` is usually a constant so the match
+/// below folds away
+#[inline(always)]
+fn parse_digits_radix<T: Unsigned, const EXACT: bool>(
+    x: &[u8],
+    radix: u32,
+) -> Result<ParseResult<T>, ParseError> {
+    if radix == 10 {
+        return parse_digits::<_, EXACT, CHECKED>(x);
+    }
+
+    // 32 is missing because 16 base 32 digits don't fit in the 64-bit partial results
+    #[cfg(target_arch = "x86_64")]
+    if matches!(radix, 2 | 4 | 8 | 16) && has_sse41() {
+        return unsafe {
+            match radix {
+                2 => crate::r#impl::parse_radix::<_, 2, EXACT, CHECKED>(x),
+                4 => crate::r#impl::parse_radix::<_, 4, EXACT, CHECKED>(x),
+                8 => crate::r#impl::parse_radix::<_, 8, EXACT, CHECKED>(x),
+                _ => crate::r#impl::parse_radix::<_, 16, EXACT, CHECKED>(x),
+            }
+        };
+    }
+
+    crate::portable::parse_digits_radix::<_, EXACT>(x, radix)
+}
+
+/// same as [`parse_digits`], rejecting numbers with more than `max_digits` digits (after
+/// the leading zeros) early on x86_64
+#[inline(always)]
+#[cfg_attr(not(target_arch = "x86_64"), allow(unused_variables))]
+fn parse_digits_bounded<T: Unsigned>(
+    x: &[u8],
+    max_digits: usize,
+) -> Result<ParseResult<T>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_bounded(x, max_digits) };
+    }
+
+    parse_digits::<_, false, CHECKED>(x)
+}
+
+/// parses the fields of `x` between delimiters with the best implementation for this CPU
+#[inline(always)]
+fn parse_fields<T: ParseInt>(x: &[u8], delimiter: u8, out: &mut Vec<T>) -> Result<(), ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_delimited(x, delimiter, out) };
+    }
+
+    crate::portable::parse_fields(x, delimiter, out)
+}
+
+/// same as [`parse_digits`] in exact mode for `x` after its first `sign_len` bytes
+#[inline(always)]
+fn parse_digits_fixed<T: Unsigned, const N: usize>(
+    x: &[u8; N],
+    sign_len: usize,
+) -> Result<ParseResult<T>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_fixed(x, sign_len) };
+    }
+
+    crate::portable::parse_digits_fixed(&x[sign_len..])
+}
+
+/// same as [`parse_digits`] for the first `len` bytes of `x`, the rest is padding
+/// only used by the SSE4.1 implementation
+#[inline(always)]
+fn parse_digits_padded<T: Unsigned>(x: &[u8], len: usize) -> Result<ParseResult<T>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_padded(x, len) };
+    }
+
+    parse_digits::<_, false, CHECKED>(&x[..len])
+}
+
+/// same as [`parse_digits`] for u128, with the specialized kernel on x86_64
+#[inline(always)]
+fn parse_digits_u128(x: &[u8]) -> Result<ParseResult<u128>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_u128(x) };
+    }
+
+    parse_digits::<_, false, CHECKED>(x)
+}
+
+/// there's no NEON version of the separator kernel yet, so aarch64 uses the portable one
+#[inline(always)]
+fn parse_digits_with_separator<T: Unsigned, const GROUPED: bool>(
+    x: &[u8],
+    separator: u8,
+) -> Result<ParseResult<T>, ParseError> {
+    #[cfg(target_arch = "x86_64")]
+    if has_sse41() {
+        return unsafe { crate::r#impl::parse_with_separator::<_, GROUPED>(x, separator) };
+    }
+
+    crate::portable::parse_digits_with_separator::<_, GROUPED>(x, separator)
+}
+
+// the SIMD implementations load whole aligned chunks, which can start before and end after
+// the input slice. Miri and the sanitizers reject those loads, so the chunks are loaded
+// only from inside of the slice when running under Miri or with the `in-bounds-loads` feature
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const IN_BOUNDS_LOADS: bool = cfg!(any(feature = "in-bounds-loads", miri));
+
+// the detection result is cached by std in an atomic after the first call, and the whole
+// check compiles down to `true` when building with `-C target-feature=+sse4.1`.
+// Without std there's no runtime detection, so only the features enabled at compile time
+// (e.g. with `-C target-cpu=native`) are used. The `force-portable` feature turns every check
+// into `false`, so that the tests run on the portable implementation
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+macro_rules! has_x86_feature {
+    ($feature:tt) => {
+        !cfg!(feature = "force-portable") && std::is_x86_feature_detected!($feature)
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+macro_rules! has_x86_feature {
+    ($feature:tt) => {
+        !cfg!(feature = "force-portable") && cfg!(target_feature = $feature)
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn has_sse41() -> bool {
+    has_x86_feature!("sse4.1")
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn has_avx2() -> bool {
+    has_x86_feature!("avx2")
+}
+
+// NEON is part of the baseline of every mainstream aarch64 target, in which case
+// this is `true` at compile time
+#[cfg(all(target_arch = "aarch64", feature = "std"))]
+#[inline(always)]
+fn has_neon() -> bool {
+    !cfg!(feature = "force-portable") && std::arch::is_aarch64_feature_detected!("neon")
+}
 
-#[cfg(target_feature = "sse4.1")]
-pub fn parse(x: &[u8]) -> ParseResult {
-    unsafe { crate::r#impl::parse(x) }
+#[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+#[inline(always)]
+fn has_neon() -> bool {
+    !cfg!(feature = "force-portable") && cfg!(target_feature = "neon")
 }
@@ -0,0 +1,329 @@
+//! NEON port of the SSE4.1 implementation, for base 10 only.
+//!
+//! Everything built on [`parse_digits`](crate::parse_digits) uses it, including the bounded,
+//! padded and u128 variants (without their x86_64 kernels). Hex and other radixes, digit
+//! separators, [`parse_fixed`](crate::parse_fixed) and [`parse_delimited`](crate::parse_delimited)
+//! fall back to the portable implementation.
+
+use crate::ParseResult;
+use crate::error::ParseError;
+use crate::hint::cold_path;
+use crate::hint::likely;
+use crate::int::sealed::Unsigned;
+use core::arch::aarch64::uint8x16_t;
+use core::arch::aarch64::vandq_u8;
+use core::arch::aarch64::vcgtq_u8;
+use core::arch::aarch64::vcltq_u8;
+use core::arch::aarch64::vdupq_n_u8;
+use core::arch::aarch64::vget_lane_u64;
+use core::arch::aarch64::vgetq_lane_u64;
+use core::arch::aarch64::vld1q_u8;
+use core::arch::aarch64::vld1q_u16;
+use core::arch::aarch64::vld1q_u32;
+use core::arch::aarch64::vmulq_u8;
+use core::arch::aarch64::vmulq_u16;
+use core::arch::aarch64::vmulq_u32;
+use core::arch::aarch64::vpaddlq_u8;
+use core::arch::aarch64::vpaddlq_u16;
+use core::arch::aarch64::vpaddlq_u32;
+use core::arch::aarch64::vqtbl1q_u8;
+use core::arch::aarch64::vreinterpret_u64_u8;
+use core::arch::aarch64::vreinterpretq_u16_u8;
+use core::arch::aarch64::vshrn_n_u16;
+use core::arch::aarch64::vsubq_u8;
+
+// same as r#impl::parse, see the comments there for the details of the algorithm
+#[inline]
+#[target_feature(enable = "neon")]
+pub fn parse<T: Unsigned, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
+    if x.len() <= 6 {
+        let mut value = 0_u64;
+        let mut len = 0;
+
+        for (i, byte) in x.iter().enumerate() {
+            let c = byte.wrapping_sub(0x30);
+            if c > 9 {
+                break;
+            }
+
+            value = value * 10 + c as u64;
+            len = i + 1;
+        }
+
+        if EXACT && len != x.len() {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        let Some(value) = T::from_u64_with::<OVERFLOW>(value) else {
+            cold_path();
+            return Err(ParseError::Overflow { len });
+        };
+
+        return Ok(ParseResult { value, len });
+    }
+
+    // realign the chunks to 16 bytes, see load_chunk
+    let offset = chunk_offset(x);
+    let mut chunk = load_chunk(x, offset, 0);
+
+    chunk = shift_right_8x16(chunk, offset);
+
+    let mut result = parse_last_chars(chunk, x.len().min(16) as u8);
+    let mut i = result.len;
+
+    if EXACT && result.len != x.len().min(16 - offset) {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    let Some(mut value) = T::from_u64_with::<OVERFLOW>(result.value) else {
+        cold_path();
+        return Err(ParseError::Overflow {
+            len: i + count_digits(&x[i..]),
+        });
+    };
+
+    // string is not all digits
+    if result.len != 16 - offset {
+        return Ok(ParseResult { value, len: i });
+    }
+
+    if likely(x.len() - i <= 16) {
+        let chunk_len = (x.len() - i) as u8;
+        chunk = load_chunk(x, offset, 1);
+
+        let result = parse_last_chars(chunk, chunk_len);
+        i += result.len;
+
+        if EXACT && i != x.len() {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        let Some(new_value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits(&x[i..]),
+            });
+        };
+
+        return Ok(ParseResult {
+            value: new_value,
+            len: i,
+        });
+    }
+
+    // strings with length > 32
+
+    let mut loops = 1_usize;
+
+    loop {
+        chunk = load_chunk(x, offset, loops);
+
+        let result = parse_16_chars(chunk);
+        i += result.len;
+        loops += 1;
+
+        if EXACT && result.len != 16 {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        let Some(new_value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: i + count_digits(&x[i..]),
+            });
+        };
+
+        value = new_value;
+
+        if result.len != 16 {
+            return Ok(ParseResult { value, len: i });
+        }
+
+        if x.len() - i <= 16 {
+            break;
+        }
+    }
+
+    // last round: we parse the remaining < 16 bytes, loading past the end of the slice
+
+    let chunk_len = (x.len() - i) as u8;
+    chunk = load_chunk(x, offset, loops);
+
+    result = parse_last_chars(chunk, chunk_len);
+    i += result.len;
+
+    if EXACT && i != x.len() {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    let Some(value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+        cold_path();
+        return Err(ParseError::Overflow {
+            len: i + count_digits(&x[i..]),
+        });
+    };
+
+    Ok(ParseResult { value, len: i })
+}
+
+/// same as r#impl::chunk_offset
+#[inline(always)]
+fn chunk_offset(x: &[u8]) -> usize {
+    if crate::IN_BOUNDS_LOADS {
+        0
+    } else {
+        x.as_ptr().addr() & 0b1111_usize
+    }
+}
+
+/// same as r#impl::load_chunk
+#[inline]
+#[target_feature(enable = "neon")]
+fn load_chunk(x: &[u8], offset: usize, index: usize) -> uint8x16_t {
+    if crate::IN_BOUNDS_LOADS {
+        let start = 16 * index;
+
+        if x.len() - start >= 16 {
+            return unsafe { vld1q_u8(x.as_ptr().add(start)) };
+        }
+
+        let mut tail = [0_u8; 16];
+        tail[..x.len() - start].copy_from_slice(&x[start..]);
+        return unsafe { vld1q_u8(tail.as_ptr()) };
+    }
+
+    // like in the SSE4.1 version this loads from before the start and after the end of the slice
+    unsafe { vld1q_u8(x.as_ptr().wrapping_sub(offset).wrapping_add(16 * index)) }
+}
+
+/// same as r#impl::count_digits
+#[cold]
+#[target_feature(enable = "neon")]
+fn count_digits(x: &[u8]) -> usize {
+    let mut i = 0;
+
+    loop {
+        let chunk = if x.len() - i >= 16 {
+            unsafe { vld1q_u8(x.as_ptr().add(i)) }
+        } else {
+            let mut tail = [0_u8; 16];
+            tail[..x.len() - i].copy_from_slice(&x[i..]);
+            unsafe { vld1q_u8(tail.as_ptr()) }
+        };
+
+        let digits = parse_16_chars(chunk).len;
+        i += digits;
+
+        if digits != 16 {
+            return i;
+        }
+    }
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn parse_16_chars(input: uint8x16_t) -> ParseResult<u64> {
+    static TENS: [u8; 16] = [10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1, 10, 1];
+    static HUNDREDS: [u16; 8] = [100, 1, 100, 1, 100, 1, 100, 1];
+    static TEN_THOUSANDS: [u32; 4] = [10000, 1, 10000, 1];
+
+    // subtract '0' so that digits are translated into their value
+    let mut chunk = vsubq_u8(input, vdupq_n_u8(0x30));
+
+    // NEON has unsigned comparisons so (unlike SSE) we don't need to wrap the values around
+    let is_non_digit_bytemask = vcgtq_u8(chunk, vdupq_n_u8(9));
+
+    // NEON has no movemask, but narrowing each 16-bit lane with a shift by 4 leaves
+    // a 4-bit mask for each byte in a 64-bit value. If all chars are digits
+    // the mask is zero and trailing_zeros returns 64, i.e. a digit count of 16
+    let is_non_digit_nibblemask = vget_lane_u64::<0>(vreinterpret_u64_u8(vshrn_n_u16::<4>(
+        vreinterpretq_u16_u8(is_non_digit_bytemask),
+    )));
+
+    let digit_count = (is_non_digit_nibblemask.trailing_zeros() / 4) as usize;
+    let non_digit_count = 16_usize - digit_count;
+
+    // shift away everything starting from the first trailing non-digit
+    chunk = shift_left_8x16(chunk, non_digit_count);
+
+    // multiply every other digit by 10 (it fits in a byte) and add the pairs into 16-bit lanes
+    // e.g. 9 | 2 | 1 | 5 becomes 92 | 15
+    let pairs = vpaddlq_u8(vmulq_u8(chunk, unsafe { vld1q_u8(TENS.as_ptr()) }));
+
+    // again with each group of four digits into 32-bit lanes
+    // e.g. 92 | 15 becomes 9215
+    let quads = vpaddlq_u16(vmulq_u16(pairs, unsafe { vld1q_u16(HUNDREDS.as_ptr()) }));
+
+    // again with groups of eight digits into 64-bit lanes
+    let octs = vpaddlq_u32(vmulq_u32(quads, unsafe {
+        vld1q_u32(TEN_THOUSANDS.as_ptr())
+    }));
+
+    // and finally the two groups of eight digits
+    let r_high = vgetq_lane_u64::<0>(octs);
+    let r_low = vgetq_lane_u64::<1>(octs);
+
+    ParseResult {
+        value: r_high * 100000000 + r_low,
+        len: digit_count,
+    }
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn parse_last_chars(chunk: uint8x16_t, len: u8) -> ParseResult<u64> {
+    static INDICES: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    // we've loaded 16 bytes so we need to mask the ones after the end of the string
+    let indices = unsafe { vld1q_u8(INDICES.as_ptr()) };
+    let is_before_end_mask = vcltq_u8(indices, vdupq_n_u8(len));
+
+    let chunk = vandq_u8(chunk, is_before_end_mask);
+
+    parse_16_chars(chunk)
+}
+
+// equivalents of utils::shift_left_8x16 and utils::shift_right_8x16, table lookups
+// with out of range indices (0x80) yield zero just like _mm_shuffle_epi8 does
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn shift_left_8x16(x: uint8x16_t, amount: usize) -> uint8x16_t {
+    #[rustfmt::skip]
+    static SHUFFLE_LUT: [u8; 32] = [
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+        0, 1, 2, 3,
+        4, 5, 6, 7,
+        8, 9, 10, 11,
+        12, 13, 14, 15,
+    ];
+
+    let shuffle = unsafe { vld1q_u8(SHUFFLE_LUT.as_ptr().add(16 - amount)) };
+
+    vqtbl1q_u8(x, shuffle)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn shift_right_8x16(x: uint8x16_t, amount: usize) -> uint8x16_t {
+    #[rustfmt::skip]
+    static SHUFFLE_LUT: [u8; 32] = [
+        0, 1, 2, 3,
+        4, 5, 6, 7,
+        8, 9, 10, 11,
+        12, 13, 14, 15,
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80,
+    ];
+
+    let shuffle = unsafe { vld1q_u8(SHUFFLE_LUT.as_ptr().add(amount)) };
+
+    vqtbl1q_u8(x, shuffle)
+}
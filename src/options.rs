@@ -0,0 +1,118 @@
+use crate::ParseResult;
+use crate::error::ParseError;
+use crate::int::ParseInt;
+
+/// Builder for parsers with a different behaviour than [`parse`](crate::parse).
+///
+/// Start from [`ParseOptions::new`], which behaves like [`parse`](crate::parse), and chain
+/// the methods below, e.g. `ParseOptions::<u32>::new().without_sign().exact().radix::<16>()`.
+/// Every option except [`max`](ParseOptions::max) is a const parameter of the type, so each
+/// combination compiles to its own parser without runtime checks for the options it
+/// doesn't use.
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions<
+    T,
+    const SIGN: bool = true,
+    const LEADING_ZEROS: bool = true,
+    const EXACT: bool = false,
+    const RADIX: u32 = 10,
+> {
+    max: Option<T>,
+}
+
+impl<T: ParseInt> ParseOptions<T> {
+    /// Options that parse like [`parse`](crate::parse): decimal digits, an optional sign
+    /// for signed types and leading zeros are accepted, and parsing stops at the first non-digit.
+    pub const fn new() -> Self {
+        ParseOptions { max: None }
+    }
+}
+
+impl<T: ParseInt> Default for ParseOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ParseInt, const SIGN: bool, const LEADING_ZEROS: bool, const EXACT: bool, const RADIX: u32>
+    ParseOptions<T, SIGN, LEADING_ZEROS, EXACT, RADIX>
+{
+    /// Rejects a leading `+` or `-` for signed types too, as if `x` didn't start with a digit.
+    pub const fn without_sign(self) -> ParseOptions<T, false, LEADING_ZEROS, EXACT, RADIX> {
+        ParseOptions { max: self.max }
+    }
+
+    /// Rejects numbers with leading zeros like [`parse_canonical`](crate::parse_canonical),
+    /// i.e. a zero followed by any digit of the radix.
+    pub const fn without_leading_zeros(self) -> ParseOptions<T, SIGN, false, EXACT, RADIX> {
+        ParseOptions { max: self.max }
+    }
+
+    /// Requires the whole input to be a number like [`parse_exact`](crate::parse_exact).
+    pub const fn exact(self) -> ParseOptions<T, SIGN, LEADING_ZEROS, true, RADIX> {
+        ParseOptions { max: self.max }
+    }
+
+    /// Parses digits in base `NEW_RADIX` like [`parse_radix`](crate::parse_radix),
+    /// a radix outside of 2 to 36 fails to compile.
+    pub const fn radix<const NEW_RADIX: u32>(
+        self,
+    ) -> ParseOptions<T, SIGN, LEADING_ZEROS, EXACT, NEW_RADIX> {
+        const {
+            assert!(
+                NEW_RADIX >= 2 && NEW_RADIX <= 36,
+                "radix must be between 2 and 36"
+            )
+        };
+
+        ParseOptions { max: self.max }
+    }
+
+    /// Rejects numbers larger than `max` with [`ParseError::OutOfRange`].
+    pub const fn max(self, max: T) -> Self {
+        ParseOptions { max: Some(max) }
+    }
+
+    /// Parses `x` with these options. On failure the returned [`ParseResult`] has `len == 0`,
+    /// use [`try_parse`](ParseOptions::try_parse) to know why.
+    pub fn parse(&self, x: &[u8]) -> ParseResult<T> {
+        self.try_parse(x).unwrap_or(ParseResult {
+            value: T::ZERO,
+            len: 0,
+        })
+    }
+
+    /// Same as [`parse`](ParseOptions::parse), but reports why the parse failed.
+    pub fn try_parse(&self, x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        self.try_parse_with(x, crate::parse_digits_radix::<_, EXACT>)
+    }
+
+    /// parses `x` with the given implementation, which is called with RADIX
+    #[inline(always)]
+    pub(crate) fn try_parse_with(
+        &self,
+        x: &[u8],
+        parse_digits_radix: impl FnOnce(&[u8], u32) -> Result<ParseResult<T::Unsigned>, ParseError>,
+    ) -> Result<ParseResult<T>, ParseError> {
+        // unsigned types already reject signs
+        if !SIGN && matches!(x.first(), Some(b'+' | b'-')) {
+            return Err(ParseError::Empty);
+        }
+
+        let result = crate::int::parse_signed::<T>(x, |digits| {
+            if !LEADING_ZEROS {
+                crate::int::check_canonical(digits, RADIX)?;
+            }
+
+            parse_digits_radix(digits, RADIX)
+        })?;
+
+        if let Some(max) = self.max
+            && result.value > max
+        {
+            return Err(ParseError::OutOfRange { len: result.len });
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,55 @@
+/// A slice followed by at least [`PADDING`](PaddedSlice::PADDING) readable bytes,
+/// parsed by [`parse_padded`](crate::parse_padded).
+///
+/// The padding lets the SIMD implementations load 16 bytes from anywhere in the slice with
+/// a single unaligned load, like simdjson does. Its contents don't matter, they are never
+/// parsed as part of the number.
+#[derive(Clone, Copy, Debug)]
+pub struct PaddedSlice<'a> {
+    // the slice followed by the padding
+    buffer: &'a [u8],
+    len: usize,
+}
+
+impl<'a> PaddedSlice<'a> {
+    /// Number of bytes that must be readable after the end of the slice.
+    pub const PADDING: usize = 16;
+
+    /// Uses the first `len` bytes of `buffer` as the slice and the rest as padding,
+    /// returns `None` if there are less than [`PADDING`](PaddedSlice::PADDING) bytes left.
+    #[inline]
+    pub const fn new(buffer: &'a [u8], len: usize) -> Option<Self> {
+        if buffer.len() < Self::PADDING || buffer.len() - Self::PADDING < len {
+            return None;
+        }
+
+        Some(PaddedSlice { buffer, len })
+    }
+
+    /// Wraps `x` without checking that it's followed by enough padding.
+    ///
+    /// # Safety
+    ///
+    /// The [`PADDING`](PaddedSlice::PADDING) bytes after the end of `x` must be initialized
+    /// and part of the same allocation as `x`, e.g. a network buffer that always has
+    /// some slack after the data it holds.
+    #[inline]
+    pub const unsafe fn new_unchecked(x: &'a [u8]) -> Self {
+        PaddedSlice {
+            buffer: unsafe { core::slice::from_raw_parts(x.as_ptr(), x.len() + Self::PADDING) },
+            len: x.len(),
+        }
+    }
+
+    /// Returns the slice, without the padding.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u8] {
+        &self.buffer[..self.len]
+    }
+
+    /// returns the slice followed by the padding
+    #[inline]
+    pub(crate) fn with_padding(&self) -> &'a [u8] {
+        self.buffer
+    }
+}
@@ -0,0 +1,231 @@
+//! Implementation that doesn't depend on any SIMD instruction set.
+//!
+//! It parses 8 bytes at a time with SWAR (SIMD within a register) arithmetic on `u64`s
+//! and never reads outside of the input slice. The top-level functions fall back to it
+//! on targets other than x86_64 and when the CPU doesn't support SSE4.1, or always with
+//! the `force-portable` feature.
+
+use crate::ParseResult;
+use crate::error::ParseError;
+use crate::hint::cold_path;
+use crate::int::CHECKED;
+use crate::int::Groups;
+use crate::int::ParseInt;
+use crate::int::digit_value;
+use crate::int::sealed::Unsigned;
+use alloc::vec::Vec;
+
+/// parses every field of `x` between delimiters like [`crate::try_parse_exact`]
+pub(crate) fn parse_fields<T: ParseInt>(
+    x: &[u8],
+    delimiter: u8,
+    out: &mut Vec<T>,
+) -> Result<(), ParseError> {
+    for field in x.split(|&c| c == delimiter) {
+        let result = crate::int::parse_signed(field, parse_digits_fixed)?;
+        out.push(result.value);
+    }
+
+    Ok(())
+}
+
+/// same as [`parse_digits`] in exact mode, but non-digits take precedence over overflows
+/// like in the SIMD implementations of [`crate::parse_fixed`] and [`crate::parse_delimited`]
+pub(crate) fn parse_digits_fixed<T: Unsigned>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+    parse_digits::<_, true, CHECKED>(x).map_err(|error| match error {
+        ParseError::Overflow { .. } if !x.iter().all(u8::is_ascii_digit) => {
+            ParseError::InvalidDigit
+        }
+        error => error,
+    })
+}
+
+#[inline]
+pub(crate) fn parse_digits<T: Unsigned, const EXACT: bool, const OVERFLOW: u8>(
+    x: &[u8],
+) -> Result<ParseResult<T>, ParseError> {
+    let mut value = T::ZERO;
+    let mut len = 0;
+
+    // whole 8 byte words, read in-bounds
+    while x.len() - len >= 8 {
+        let word = u64::from_le_bytes(x[len..len + 8].try_into().unwrap());
+        let result = parse_8_chars(word);
+        len += result.len;
+
+        if EXACT && result.len != 8 {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        let Some(new_value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+            cold_path();
+            return Err(ParseError::Overflow {
+                len: len + count_digits(&x[len..]),
+            });
+        };
+
+        value = new_value;
+
+        if result.len != 8 {
+            return Ok(ParseResult { value, len });
+        }
+    }
+
+    // copy the last < 8 bytes to a zeroed buffer, zero bytes are not digits so
+    // parse_8_chars will stop at the end of the slice
+    let mut tail = [0_u8; 8];
+    tail[..x.len() - len].copy_from_slice(&x[len..]);
+
+    let result = parse_8_chars(u64::from_le_bytes(tail));
+    len += result.len;
+
+    if EXACT && len != x.len() {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    // the number ends in the tail
+    let Some(value) = value.mul_add_with::<OVERFLOW>(result.len, result.value) else {
+        cold_path();
+        return Err(ParseError::Overflow { len });
+    };
+
+    Ok(ParseResult { value, len })
+}
+
+/// returns the number of leading digits in `x`, to report the length of numbers that overflow
+#[cold]
+fn count_digits(x: &[u8]) -> usize {
+    x.iter().take_while(|byte| byte.is_ascii_digit()).count()
+}
+
+#[inline(always)]
+fn parse_8_chars(word: u64) -> ParseResult<u64> {
+    // the first char of the string is in the least significant byte of word.
+    // algorithm comes from: https://lemire.me/blog/2022/01/21/swar-explained-parsing-eight-digits/
+
+    const ONES: u64 = 0x0101010101010101;
+    const HIGH_BITS: u64 = 0x8080808080808080;
+
+    // find the bytes in the '0' - '9' range by adding a per-byte bias that sets the high
+    // bit when the byte is >= the lower bound. We clear the high bits beforehand so that
+    // the additions can't carry into the next byte (and we treat bytes >= 0x80 as non-digits)
+    let low_bits = word & !HIGH_BITS;
+    let at_least_zero = low_bits + ONES * (0x80 - 0x30);
+    let more_than_nine = low_bits + ONES * (0x80 - 0x3a);
+    let is_digit = at_least_zero & !more_than_nine & !word & HIGH_BITS;
+
+    let digit_count = ((!is_digit & HIGH_BITS).trailing_zeros() / 8) as usize;
+
+    if digit_count == 0 {
+        return ParseResult { value: 0, len: 0 };
+    }
+
+    // translate digits into their value and shift away everything starting from the
+    // first non-digit, shifting in zeros at the front (i.e. the least significant bytes).
+    // NOTE: the subtraction can only borrow from the bytes after the first non-digit,
+    // which are shifted away
+    let mut chunk = word.wrapping_sub(ONES * 0x30) << (8 * (8 - digit_count));
+
+    // combine pairs of digits, then groups of four and eight at once
+    // NOTE: the products are meant to wrap, the result ends up in the upper 32 bits
+    chunk = (chunk * 10) + (chunk >> 8);
+    chunk = ((chunk & 0x000000FF000000FF).wrapping_mul(100 + (1000000 << 32)))
+        .wrapping_add(((chunk >> 16) & 0x000000FF000000FF).wrapping_mul(1 + (10000 << 32)))
+        >> 32;
+
+    ParseResult {
+        value: chunk,
+        len: digit_count,
+    }
+}
+
+/// one byte at a time, see r#impl::parse_with_separator
+#[inline]
+pub(crate) fn parse_digits_with_separator<T: Unsigned, const GROUPED: bool>(
+    x: &[u8],
+    separator: u8,
+) -> Result<ParseResult<T>, ParseError> {
+    let mut value = T::ZERO;
+    let mut overflowed = false;
+    let mut groups = Groups::default();
+    let mut len = 0;
+
+    while let Some(&byte) = x.get(len) {
+        if byte.is_ascii_digit() {
+            if let Some(new_value) = value.checked_mul_add(1, (byte - b'0') as u64) {
+                value = new_value;
+            } else {
+                // we keep going to find the end of the number
+                cold_path();
+                overflowed = true;
+            }
+
+            groups.add(1);
+        } else if byte == separator
+            && len > 0
+            && x[len - 1].is_ascii_digit()
+            && x.get(len + 1).is_some_and(u8::is_ascii_digit)
+        {
+            if GROUPED {
+                groups.separator()?;
+            }
+        } else {
+            break;
+        }
+
+        len += 1;
+    }
+
+    if overflowed {
+        return Err(ParseError::Overflow { len });
+    }
+
+    if GROUPED {
+        groups.finish()?;
+    }
+
+    Ok(ParseResult { value, len })
+}
+
+/// one digit at a time for any radix between 2 and 36, except for radix 10
+/// which uses parse_digits
+#[inline]
+pub(crate) fn parse_digits_radix<T: Unsigned, const EXACT: bool>(
+    x: &[u8],
+    radix: u32,
+) -> Result<ParseResult<T>, ParseError> {
+    if radix == 10 {
+        return parse_digits::<_, EXACT, CHECKED>(x);
+    }
+
+    let mut value = T::ZERO;
+    let mut len = 0;
+
+    for byte in x {
+        let c = digit_value(*byte);
+        if c as u32 >= radix {
+            break;
+        }
+
+        len += 1;
+
+        let Some(new_value) = value.checked_mul_add_radix(radix, 1, c as u64) else {
+            cold_path();
+
+            let rest = x[len..]
+                .iter()
+                .take_while(|byte| (digit_value(**byte) as u32) < radix);
+            return Err(ParseError::Overflow {
+                len: len + rest.count(),
+            });
+        };
+
+        value = new_value;
+    }
+
+    if EXACT && len != x.len() {
+        return Err(ParseError::InvalidDigit);
+    }
+
+    Ok(ParseResult { value, len })
+}
@@ -0,0 +1,43 @@
+use crate::int::ParseInt;
+use core::num::NonZero;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseResult<T = usize> {
+    pub value: T,
+    pub len: usize,
+}
+
+impl<T: ParseInt> From<ParseResult<T>> for Option<T> {
+    fn from(result: ParseResult<T>) -> Self {
+        if result.len == 0 {
+            None
+        } else {
+            Some(result.value)
+        }
+    }
+}
+
+macro_rules! impl_from_nonzero {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<ParseResult<$ty>> for Option<NonZero<$ty>> {
+                fn from(result: ParseResult<$ty>) -> Self {
+                    Option::<$ty>::from(result).and_then(NonZero::new)
+                }
+            }
+
+            impl From<ParseResult<NonZero<$ty>>> for ParseResult<$ty> {
+                fn from(result: ParseResult<NonZero<$ty>>) -> Self {
+                    ParseResult {
+                        value: result.value.get(),
+                        len: result.len,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_nonzero!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
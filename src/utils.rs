@@ -1,31 +1,8 @@
-use std::arch::x86_64::__m128i;
-use std::arch::x86_64::_mm_lddqu_si128;
-use std::arch::x86_64::_mm_set_epi8;
-use std::arch::x86_64::_mm_set_epi16;
-use std::arch::x86_64::_mm_shuffle_epi8;
-
-pub static POWERS_OF_TEN: [usize; 20] = [
-    1,
-    10,
-    100,
-    1000,
-    10000,
-    100000,
-    1000000,
-    10000000,
-    100000000,
-    1000000000,
-    10000000000,
-    100000000000,
-    1000000000000,
-    10000000000000,
-    100000000000000,
-    1000000000000000,
-    10000000000000000,
-    100000000000000000,
-    1000000000000000000,
-    10000000000000000000,
-];
+use core::arch::x86_64::__m128i;
+use core::arch::x86_64::_mm_lddqu_si128;
+use core::arch::x86_64::_mm_set_epi8;
+use core::arch::x86_64::_mm_set_epi16;
+use core::arch::x86_64::_mm_shuffle_epi8;
 
 #[inline]
 #[target_feature(enable = "sse2")]
@@ -56,9 +33,8 @@ pub fn shift_left_8x16(x: __m128i, amount: usize) -> __m128i {
         12, 13, 14, 15,
     ];
 
-    let shuffle = unsafe {
-        _mm_lddqu_si128(SHUFFLE_LUT.get_unchecked(16 - amount) as *const i8 as *const __m128i)
-    };
+    let shuffle =
+        unsafe { _mm_lddqu_si128(SHUFFLE_LUT.as_ptr().add(16 - amount) as *const __m128i) };
 
     _mm_shuffle_epi8(x, shuffle)
 }
@@ -78,9 +54,7 @@ pub fn shift_right_8x16(x: __m128i, amount: usize) -> __m128i {
         -128, -128, -128, -128,
     ];
 
-    let shuffle = unsafe {
-        _mm_lddqu_si128(SHUFFLE_LUT.get_unchecked(amount) as *const i8 as *const __m128i)
-    };
+    let shuffle = unsafe { _mm_lddqu_si128(SHUFFLE_LUT.as_ptr().add(amount) as *const __m128i) };
 
     _mm_shuffle_epi8(x, shuffle)
 }
@@ -1,8 +1,18 @@
 use std::alloc::Layout;
 use std::alloc::alloc;
+use std::alloc::dealloc;
 use std::ptr::slice_from_raw_parts_mut;
 
-use simd_parse_int::{self, ParseResult};
+use std::fmt::Debug;
+use std::iter::StepBy;
+use std::num::NonZero;
+
+use simd_parse_int::{
+    self, ParseError, ParseInt, ParseOptions, ParseResult, try_parse, try_parse_bounded,
+    try_parse_canonical, try_parse_exact, try_parse_fixed, try_parse_hex, try_parse_nonzero,
+    try_parse_padded, try_parse_prefixed, try_parse_with_separator,
+    try_parse_with_thousands_separator,
+};
 
 #[test]
 fn test_parse() {
@@ -27,7 +37,7 @@ fn test_parse() {
     do_test(123456789123456789, 18, b"123456789123456789");
     do_test(1234567891234567891, 19, b"1234567891234567891");
     do_test(12345678912345678912, 20, b"12345678912345678912");
-    do_test(std::usize::MAX, 20, b"18446744073709551615");
+    do_test(usize::MAX, 20, b"18446744073709551615");
 }
 
 #[test]
@@ -53,7 +63,7 @@ fn test_parse_trailing_garbage() {
     do_test(123456789123456789, 18, b"123456789123456789aa11");
     do_test(1234567891234567891, 19, b"1234567891234567891aa11");
     do_test(12345678912345678912, 20, b"12345678912345678912aa11");
-    do_test(std::usize::MAX, 20, b"18446744073709551615aa11");
+    do_test(usize::MAX, 20, b"18446744073709551615aa11");
 }
 
 #[test]
@@ -62,6 +72,28 @@ fn test_parse_leading_zeros() {
     do_test(2222221343435542, 20, b"00002222221343435542");
 }
 
+#[test]
+fn test_parse_long_leading_zeros() {
+    let mut input = vec![b'0'; 100];
+    input.extend_from_slice(b"18446744073709551615");
+
+    for zeros in [12, 13, 20, 31, 32, 33, 48, 63, 64, 65, 100] {
+        let input = &input[100 - zeros..];
+        do_test(usize::MAX, input.len(), input);
+    }
+
+    let mut input = vec![b'0'; 100];
+    input.extend_from_slice(b"1234567aa11");
+    do_test(1234567, 107, &input);
+
+    do_test(0, 100, &[b'0'; 100]);
+    do_test(
+        0,
+        0,
+        b"000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000",
+    );
+}
+
 #[test]
 fn test_parse_from_middle() {
     let input = b"1111123456789102232343455234";
@@ -78,14 +110,1630 @@ fn test_parse_from_middle() {
     assert!(ptr.addr() % 16 == 0);
 
     do_test(1123456, 7, unsafe { &slice.as_ref().unwrap()[3..10] });
+
+    unsafe { dealloc(ptr, layout) };
 }
 
-fn do_test(expected_value: usize, expected_len: usize, input: &[u8]) {
+#[test]
+fn test_parse_u8() {
+    do_test_parse(try_parse, Ok((0_u8, 1)), b"0");
+    do_test_parse(try_parse, Ok((9_u8, 1)), b"9");
+    do_test_parse(try_parse, Ok((99_u8, 2)), b"99");
+    do_test_parse(try_parse, Ok((255_u8, 3)), b"255");
+    do_test_parse(try_parse, Ok((255_u8, 3)), b"255aa11");
+    do_test_parse(try_parse, Ok((255_u8, 20)), b"00000000000000000255");
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"256",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"1000",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 7 }),
+        b"1234567",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"00000000000000001000",
+    );
+    do_test_parse(try_parse, Ok((255_u8, 16)), b"0000000000000255");
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 16 }),
+        b"0000000000000256",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 16 }),
+        b"0000000000001000",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 16 }),
+        b"1000000000000000",
+    );
+}
+
+#[test]
+fn test_parse_u16() {
+    do_test_parse(try_parse, Ok((0_u16, 1)), b"0");
+    do_test_parse(try_parse, Ok((12345_u16, 5)), b"12345");
+    do_test_parse(try_parse, Ok((u16::MAX, 5)), b"65535");
+    do_test_parse(try_parse, Ok((u16::MAX, 5)), b"65535aa11");
+    do_test_parse(try_parse, Ok((u16::MAX, 20)), b"00000000000000065535");
+    do_test_parse(
+        try_parse::<u16>,
+        Err(ParseError::Overflow { len: 5 }),
+        b"65536",
+    );
+    do_test_parse(
+        try_parse::<u16>,
+        Err(ParseError::Overflow { len: 6 }),
+        b"100000",
+    );
+    do_test_parse(
+        try_parse::<u16>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"12345678912345678912",
+    );
+    do_test_parse(try_parse, Ok((u16::MAX, 13)), b"0000000065535");
+    do_test_parse(
+        try_parse::<u16>,
+        Err(ParseError::Overflow { len: 13 }),
+        b"0000000100000",
+    );
+}
+
+#[test]
+fn test_parse_u32() {
+    do_test_parse(try_parse, Ok((0_u32, 1)), b"0");
+    do_test_parse(try_parse, Ok((123456789_u32, 9)), b"123456789");
+    do_test_parse(try_parse, Ok((u32::MAX, 10)), b"4294967295");
+    do_test_parse(try_parse, Ok((u32::MAX, 10)), b"4294967295aa11");
+    do_test_parse(try_parse, Ok((u32::MAX, 20)), b"00000000004294967295");
+    do_test_parse(
+        try_parse,
+        Ok((u32::MAX, 40)),
+        b"0000000000000000000000000000004294967295",
+    );
+    do_test_parse(
+        try_parse::<u32>,
+        Err(ParseError::Overflow { len: 10 }),
+        b"4294967296",
+    );
+    do_test_parse(
+        try_parse::<u32>,
+        Err(ParseError::Overflow { len: 11 }),
+        b"10000000000",
+    );
+    do_test_parse(try_parse, Ok((u32::MAX, 15)), b"000004294967295");
+    do_test_parse(
+        try_parse::<u32>,
+        Err(ParseError::Overflow { len: 15 }),
+        b"000010000000000",
+    );
+    do_test_parse(
+        try_parse::<u32>,
+        Err(ParseError::Overflow { len: 22 }),
+        b"1000000000000000000000",
+    );
+}
+
+#[test]
+fn test_parse_u64() {
+    do_test_parse(try_parse, Ok((0_u64, 1)), b"0");
+    do_test_parse(
+        try_parse,
+        Ok((1234567891234567891_u64, 19)),
+        b"1234567891234567891",
+    );
+    do_test_parse(try_parse, Ok((u64::MAX, 20)), b"18446744073709551615");
+    do_test_parse(try_parse, Ok((u64::MAX, 20)), b"18446744073709551615aa11");
+    do_test_parse(
+        try_parse,
+        Ok((u64::MAX, 40)),
+        b"0000000000000000000018446744073709551615",
+    );
+    do_test_parse(
+        try_parse::<u64>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616",
+    );
+    do_test_parse(
+        try_parse::<u64>,
+        Err(ParseError::Overflow { len: 21 }),
+        b"100000000000000000000",
+    );
+}
+
+#[test]
+fn test_parse_u128() {
+    // the u128 kernel must agree with the generic one
+    fn parse(x: &[u8]) -> Result<ParseResult<u128>, ParseError> {
+        let result = simd_parse_int::try_parse_u128(x);
+        assert_eq!(try_parse(x), result);
+        result
+    }
+
+    do_test_parse(parse, Ok((0, 1)), b"0");
+    do_test_parse(
+        parse,
+        Ok((12345678912345678912, 20)),
+        b"12345678912345678912",
+    );
+    do_test_parse(
+        parse,
+        Ok((123456789123456789123456789, 27)),
+        b"123456789123456789123456789",
+    );
+    do_test_parse(
+        parse,
+        Ok((u128::MAX, 39)),
+        b"340282366920938463463374607431768211455",
+    );
+    do_test_parse(
+        parse,
+        Ok((u128::MAX, 39)),
+        b"340282366920938463463374607431768211455aa11",
+    );
+    do_test_parse(
+        parse,
+        Ok((u128::MAX, 50)),
+        b"00000000000340282366920938463463374607431768211455",
+    );
+    do_test_parse(
+        parse,
+        Err(ParseError::Overflow { len: 39 }),
+        b"340282366920938463463374607431768211456",
+    );
+    do_test_parse(
+        parse,
+        Err(ParseError::Overflow { len: 40 }),
+        b"1000000000000000000000000000000000000000",
+    );
+
     assert_eq!(
-        simd_parse_int::parse(input),
+        simd_parse_int::try_parse_u128(b"340282366920938463463374607431768211456"),
+        Err(ParseError::Overflow { len: 39 })
+    );
+    assert_eq!(
+        simd_parse_int::try_parse_u128(b"-1"),
+        Err(ParseError::Empty)
+    );
+
+    // every number of digits around u128::MAX and every number of leading zeros
+    // up to more than three chunks, starting at every alignment
+    for number in [
+        &b"340282366920938463463374607431768211455"[..],
+        b"340282366920938463463374607431768211456",
+        b"340282366920938463463374607431768211455000",
+    ] {
+        for digits in 1..=number.len() {
+            let reference = std::str::from_utf8(&number[..digits])
+                .unwrap()
+                .parse::<u128>();
+
+            for zeros in [0, 1, 9, 15, 16, 17, 31, 32, 33, 48, 60] {
+                let mut buffer = vec![b'0'; 16 + zeros];
+                buffer.extend_from_slice(&number[..digits]);
+                buffer.push(b',');
+
+                for start in sampled(0..16) {
+                    let input = &buffer[start..];
+                    let number_len = input.len() - 1;
+                    let expected = match reference {
+                        Ok(value) => Ok(ParseResult {
+                            value,
+                            len: number_len,
+                        }),
+                        Err(_) => Err(ParseError::Overflow { len: number_len }),
+                    };
+
+                    for input in [input, &input[..number_len]] {
+                        assert_eq!(simd_parse_int::try_parse_u128(input), expected);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_padded() {
+    fn parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        // digits in the padding must not be parsed
+        let mut buffer = x.to_vec();
+        buffer.extend_from_slice(b"1111111111111111");
+        let padded = simd_parse_int::PaddedSlice::new(&buffer, x.len()).unwrap();
+
+        let result = try_parse_padded(padded);
+        assert_eq!(try_parse(x), result);
+        result
+    }
+
+    do_test_parse(parse, Ok((0_u8, 1)), b"0");
+    do_test_parse(parse, Ok((255_u8, 3)), b"255");
+    do_test_parse(parse::<u8>, Err(ParseError::Overflow { len: 3 }), b"256");
+    do_test_parse(parse, Ok((-128_i8, 4)), b"-128");
+    do_test_parse(
+        parse,
+        Ok((1234567891234567891_u64, 19)),
+        b"1234567891234567891",
+    );
+    do_test_parse(parse, Ok((u64::MAX, 20)), b"18446744073709551615aa11");
+    do_test_parse(
+        parse,
+        Ok((u64::MAX, 40)),
+        b"0000000000000000000018446744073709551615",
+    );
+    do_test_parse(parse, Ok((i64::MIN, 20)), b"-9223372036854775808");
+    do_test_parse(
+        parse::<u64>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616",
+    );
+    do_test_parse(
+        parse,
+        Ok((u128::MAX, 39)),
+        b"340282366920938463463374607431768211455",
+    );
+    do_test_parse(parse::<u32>, Err(ParseError::Empty), b"");
+    do_test_parse(parse::<u32>, Err(ParseError::Empty), b"-");
+    do_test_parse(parse::<u32>, Err(ParseError::Empty), b"a1");
+
+    assert!(simd_parse_int::PaddedSlice::new(b"123", 0).is_none());
+    assert!(simd_parse_int::PaddedSlice::new(&[b'1'; 18], 3).is_none());
+    assert!(simd_parse_int::PaddedSlice::new(&[b'1'; 18], 2).is_some());
+    assert!(simd_parse_int::PaddedSlice::new(&[b'1'; 18], usize::MAX).is_none());
+
+    // every number of digits and leading zeros, starting at every alignment
+    for number in [&b"18446744073709551615"[..], b"18446744073709551616"] {
+        for digits in 1..=number.len() {
+            for zeros in [0, 1, 15, 16, 17, 32, 40] {
+                let mut buffer = vec![b'0'; 16 + zeros];
+                buffer.extend_from_slice(&number[..digits]);
+
+                for start in sampled(0..16) {
+                    let input = &buffer[start..];
+                    let expected = simd_parse_int::try_parse::<u64>(input);
+
+                    let mut padded = input.to_vec();
+                    padded.extend_from_slice(b"1111111111111111");
+                    let x = simd_parse_int::PaddedSlice::new(&padded, input.len()).unwrap();
+
+                    assert_eq!(simd_parse_int::try_parse_padded::<u64>(x), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_sign() {
+    do_test_parse(try_parse, Ok((1_i64, 2)), b"+1");
+    do_test_parse(try_parse, Ok((-1_i64, 2)), b"-1");
+    do_test_parse(try_parse, Ok((0_i64, 2)), b"-0");
+    do_test_parse(try_parse, Ok((-123456_i64, 7)), b"-123456aa11");
+    do_test_parse(try_parse, Ok((-1234567_i64, 8)), b"-1234567");
+    do_test_parse(
+        try_parse,
+        Ok((-12345678912345678_i64, 18)),
+        b"-12345678912345678aa11",
+    );
+    do_test_parse(
+        try_parse,
+        Ok((-2222221343435542_i64, 21)),
+        b"-00002222221343435542",
+    );
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"-");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"+");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"--1");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"+-1");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"-aa11");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b" -1");
+    do_test_parse(try_parse::<u64>, Err(ParseError::Empty), b"-1");
+    do_test_parse(try_parse::<u64>, Err(ParseError::Empty), b"+1");
+}
+
+#[test]
+fn test_parse_i8() {
+    do_test_parse(try_parse, Ok((i8::MAX, 3)), b"127");
+    do_test_parse(try_parse, Ok((i8::MAX, 4)), b"+127");
+    do_test_parse(try_parse, Ok((i8::MIN, 4)), b"-128");
+    do_test_parse(try_parse, Ok((i8::MIN, 21)), b"-00000000000000000128");
+    do_test_parse(
+        try_parse::<i8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"128",
+    );
+    do_test_parse(
+        try_parse::<i8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"-129",
+    );
+    do_test_parse(
+        try_parse::<i8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"-256",
+    );
+}
+
+#[test]
+fn test_parse_i16() {
+    do_test_parse(try_parse, Ok((i16::MAX, 5)), b"32767");
+    do_test_parse(try_parse, Ok((i16::MIN, 6)), b"-32768");
+    do_test_parse(
+        try_parse::<i16>,
+        Err(ParseError::Overflow { len: 5 }),
+        b"32768",
+    );
+    do_test_parse(
+        try_parse::<i16>,
+        Err(ParseError::Overflow { len: 6 }),
+        b"-32769",
+    );
+}
+
+#[test]
+fn test_parse_i32() {
+    do_test_parse(try_parse, Ok((i32::MAX, 10)), b"2147483647");
+    do_test_parse(try_parse, Ok((i32::MIN, 11)), b"-2147483648");
+    do_test_parse(
+        try_parse::<i32>,
+        Err(ParseError::Overflow { len: 10 }),
+        b"2147483648",
+    );
+    do_test_parse(
+        try_parse::<i32>,
+        Err(ParseError::Overflow { len: 11 }),
+        b"-2147483649",
+    );
+}
+
+#[test]
+fn test_parse_i64() {
+    do_test_parse(try_parse, Ok((i64::MAX, 19)), b"9223372036854775807");
+    do_test_parse(try_parse, Ok((i64::MAX, 20)), b"+9223372036854775807");
+    do_test_parse(try_parse, Ok((i64::MIN, 20)), b"-9223372036854775808");
+    do_test_parse(try_parse, Ok((i64::MIN, 20)), b"-9223372036854775808aa11");
+    do_test_parse(
+        try_parse,
+        Ok((i64::MIN, 41)),
+        b"-0000000000000000000009223372036854775808",
+    );
+    do_test_parse(
+        try_parse::<i64>,
+        Err(ParseError::Overflow { len: 19 }),
+        b"9223372036854775808",
+    );
+    do_test_parse(
+        try_parse::<i64>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"-9223372036854775809",
+    );
+    do_test_parse(
+        try_parse::<i64>,
+        Err(ParseError::Overflow { len: 21 }),
+        b"-18446744073709551616",
+    );
+}
+
+#[test]
+fn test_parse_i128() {
+    do_test_parse(
+        try_parse,
+        Ok((i128::MAX, 39)),
+        b"170141183460469231731687303715884105727",
+    );
+    do_test_parse(
+        try_parse,
+        Ok((i128::MIN, 40)),
+        b"-170141183460469231731687303715884105728",
+    );
+    do_test_parse(
+        try_parse::<i128>,
+        Err(ParseError::Overflow { len: 39 }),
+        b"170141183460469231731687303715884105728",
+    );
+    do_test_parse(
+        try_parse::<i128>,
+        Err(ParseError::Overflow { len: 40 }),
+        b"-170141183460469231731687303715884105729",
+    );
+}
+
+#[test]
+fn test_try_parse_errors() {
+    do_test_parse(try_parse::<usize>, Err(ParseError::Empty), b"");
+    do_test_parse(try_parse::<usize>, Err(ParseError::Empty), b"abc");
+    do_test_parse(
+        try_parse::<usize>,
+        Err(ParseError::Empty),
+        b"aa11111111111111111111",
+    );
+    do_test_parse(try_parse::<usize>, Err(ParseError::Empty), b"-1");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"-");
+    do_test_parse(try_parse::<i64>, Err(ParseError::Empty), b"+abc");
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"256",
+    );
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"1000aa11",
+    );
+    do_test_parse(
+        try_parse::<i8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"-129",
+    );
+    do_test_parse(
+        try_parse::<usize>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"20000000000000000000",
+    );
+    do_test_parse(
+        try_parse::<usize>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616aa11",
+    );
+    do_test_parse(
+        try_parse::<i64>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"-9223372036854775809",
+    );
+
+    do_test_parse(
+        try_parse,
+        Ok((12345678912345678912_usize, 20)),
+        b"12345678912345678912aa11",
+    );
+}
+
+#[test]
+fn test_parse_exact() {
+    do_test_parse(try_parse_exact, Ok((0_usize, 1)), b"0");
+    do_test_parse(try_parse_exact, Ok((123_usize, 3)), b"123");
+    do_test_parse(try_parse_exact, Ok((1234567_usize, 7)), b"1234567");
+    do_test_parse(
+        try_parse_exact,
+        Ok((usize::MAX, 20)),
+        b"18446744073709551615",
+    );
+    do_test_parse(
+        try_parse_exact,
+        Ok((usize::MAX, 54)),
+        b"000000000000000000000000000000000018446744073709551615",
+    );
+    do_test_parse(try_parse_exact, Ok((-128_i8, 4)), b"-128");
+    do_test_parse(try_parse_exact, Ok((127_i8, 4)), b"+127");
+
+    do_test_parse(try_parse_exact::<usize>, Err(ParseError::Empty), b"");
+    do_test_parse(try_parse_exact::<i32>, Err(ParseError::Empty), b"-");
+    do_test_parse(
+        try_parse_exact::<usize>,
+        Err(ParseError::InvalidDigit),
+        b"123abc",
+    );
+    do_test_parse(
+        try_parse_exact::<usize>,
+        Err(ParseError::InvalidDigit),
+        b"abc",
+    );
+    do_test_parse(
+        try_parse_exact::<usize>,
+        Err(ParseError::InvalidDigit),
+        b"-1",
+    );
+    do_test_parse(
+        try_parse_exact::<u8>,
+        Err(ParseError::InvalidDigit),
+        b"300x",
+    );
+    do_test_parse(
+        try_parse_exact::<i32>,
+        Err(ParseError::InvalidDigit),
+        b"--1",
+    );
+    do_test_parse(
+        try_parse_exact::<u8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"256",
+    );
+    do_test_parse(
+        try_parse_exact::<usize>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616",
+    );
+
+    // a non-digit at every position of numbers of every length, starting at every alignment
+    let mut buffer = [b'0'; 80];
+    for start in sampled(0..16) {
+        for len in sampled(1..64) {
+            do_test_parse(
+                try_parse_exact,
+                Ok((0_u64, len)),
+                &buffer[start..start + len],
+            );
+
+            for position in sampled(start..start + len) {
+                buffer[position] = b'/';
+                do_test_parse(
+                    try_parse_exact::<u64>,
+                    Err(ParseError::InvalidDigit),
+                    &buffer[start..start + len],
+                );
+                buffer[position] = b'0';
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_fixed() {
+    do_test_parse(try_parse_fixed, Ok((0_usize, 1)), b"0");
+    do_test_parse(try_parse_fixed, Ok((7_u8, 3)), b"007");
+    do_test_parse(try_parse_fixed, Ok((12345678_u32, 8)), b"12345678");
+    do_test_parse(try_parse_fixed, Ok((123456789_u32, 9)), b"123456789");
+    do_test_parse(
+        try_parse_fixed,
+        Ok((1234567891234567_u64, 16)),
+        b"1234567891234567",
+    );
+    do_test_parse(try_parse_fixed, Ok((u64::MAX, 20)), b"18446744073709551615");
+    do_test_parse(
+        try_parse_fixed,
+        Ok((u64::MAX, 40)),
+        b"0000000000000000000018446744073709551615",
+    );
+    do_test_parse(
+        try_parse_fixed,
+        Ok((u128::MAX, 39)),
+        b"340282366920938463463374607431768211455",
+    );
+    do_test_parse(try_parse_fixed, Ok((-128_i8, 4)), b"-128");
+    do_test_parse(try_parse_fixed, Ok((127_i8, 4)), b"+127");
+    do_test_parse(
+        try_parse_fixed,
+        Ok((-42_i32, 24)),
+        b"-00000000000000000000042",
+    );
+    do_test_parse(try_parse_fixed, Ok((i64::MIN, 20)), b"-9223372036854775808");
+
+    do_test_parse(try_parse_fixed::<usize, 0>, Err(ParseError::Empty), b"");
+    do_test_parse(try_parse_fixed::<i32, 1>, Err(ParseError::Empty), b"-");
+    do_test_parse(
+        try_parse_fixed::<usize, 6>,
+        Err(ParseError::InvalidDigit),
+        b"123abc",
+    );
+    do_test_parse(
+        try_parse_fixed::<usize, 2>,
+        Err(ParseError::InvalidDigit),
+        b"-1",
+    );
+    do_test_parse(
+        try_parse_fixed::<i32, 3>,
+        Err(ParseError::InvalidDigit),
+        b"--1",
+    );
+    do_test_parse(
+        try_parse_fixed::<i32, 3>,
+        Err(ParseError::InvalidDigit),
+        b"1-1",
+    );
+    do_test_parse(
+        try_parse_fixed::<u8, 3>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"256",
+    );
+    do_test_parse(
+        try_parse_fixed::<i8, 4>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"-129",
+    );
+    do_test_parse(
+        try_parse_fixed::<u64, 20>,
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616",
+    );
+
+    // non-digits take precedence over overflows
+    do_test_parse(
+        try_parse_fixed::<u8, 4>,
+        Err(ParseError::InvalidDigit),
+        b"300x",
+    );
+    do_test_parse(
+        try_parse_fixed::<u64, 21>,
+        Err(ParseError::InvalidDigit),
+        b"99999999999999999999x",
+    );
+    do_test_parse(
+        try_parse_fixed::<u64, 40>,
+        Err(ParseError::InvalidDigit),
+        b"9999999999999999999x99999999999999999999",
+    );
+
+    do_test_fixed_lengths::<1>();
+    do_test_fixed_lengths::<2>();
+    do_test_fixed_lengths::<3>();
+    do_test_fixed_lengths::<4>();
+    do_test_fixed_lengths::<5>();
+    do_test_fixed_lengths::<8>();
+    do_test_fixed_lengths::<9>();
+    do_test_fixed_lengths::<15>();
+    do_test_fixed_lengths::<16>();
+    do_test_fixed_lengths::<17>();
+    do_test_fixed_lengths::<19>();
+    do_test_fixed_lengths::<20>();
+    do_test_fixed_lengths::<21>();
+    do_test_fixed_lengths::<32>();
+    do_test_fixed_lengths::<33>();
+    do_test_fixed_lengths::<39>();
+    do_test_fixed_lengths::<40>();
+    do_test_fixed_lengths::<50>();
+}
+
+#[test]
+fn test_parse_saturating() {
+    fn parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        Ok(simd_parse_int::parse_saturating(x))
+    }
+
+    do_test_parse(parse, Ok((123_u64, 3)), b"123");
+    do_test_parse(parse, Ok((u64::MAX, 20)), b"18446744073709551615");
+    do_test_parse(parse, Ok((u64::MAX, 20)), b"18446744073709551616");
+    do_test_parse(parse, Ok((u64::MAX, 21)), b"100000000000000000000");
+    do_test_parse(parse, Ok((u64::MAX, 24)), b"999999999999999999999999,");
+    do_test_parse(parse, Ok((u64::MAX, 60)), &[b'9'; 60]);
+    do_test_parse(parse, Ok((u8::MAX, 3)), b"256");
+    do_test_parse(parse, Ok((u8::MAX, 25)), b"1000000000000000000000000");
+    do_test_parse(parse, Ok((i64::MAX, 22)), b"+100000000000000000000");
+    do_test_parse(parse, Ok((i64::MIN, 22)), b"-100000000000000000000");
+    do_test_parse(parse, Ok((i8::MIN, 4)), b"-129");
+    do_test_parse(parse, Ok((i8::MIN, 4)), b"-128");
+    do_test_parse(parse, Ok((u128::MAX, 45)), &[b'9'; 45]);
+    do_test_parse(
+        parse,
+        Ok((u64::MAX, 63)),
+        b"000000000000000000000000000000000000000000100000000000000000000",
+    );
+    do_test_parse(parse, Ok((0_u64, 0)), b"abc");
+}
+
+#[test]
+fn test_parse_wrapping() {
+    fn parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        Ok(simd_parse_int::parse_wrapping(x))
+    }
+
+    do_test_parse(parse, Ok((123_u64, 3)), b"123");
+    do_test_parse(parse, Ok((u64::MAX, 20)), b"18446744073709551615");
+    do_test_parse(parse, Ok((0_u64, 20)), b"18446744073709551616");
+    do_test_parse(
+        parse,
+        Ok((10_u64.wrapping_pow(20), 21)),
+        b"100000000000000000000",
+    );
+    do_test_parse(
+        parse,
+        Ok(((123456789012345678901234567890_u128 % (1 << 64)) as u64, 30)),
+        b"123456789012345678901234567890",
+    );
+    do_test_parse(parse, Ok((0_u8, 3)), b"256");
+    do_test_parse(parse, Ok((44_u8, 3)), b"300");
+    do_test_parse(parse, Ok((44_u8, 16)), b"0000000000000300");
+    do_test_parse(parse, Ok((0_u16, 16)), b"0000000000065536");
+    do_test_parse(parse, Ok((232_u8, 16)), b"0000000000001000");
+    do_test_parse(parse, Ok((16960_u16, 16)), b"0000000001000000");
+    do_test_parse(parse, Ok((-56_i8, 3)), b"200");
+    do_test_parse(parse, Ok((-44_i8, 4)), b"-300");
+    do_test_parse(parse, Ok((i64::MIN, 20)), b"-9223372036854775808");
+    do_test_parse(parse, Ok((i64::MIN, 19)), b"9223372036854775808");
+    do_test_parse(
+        parse,
+        Ok((10_i64.wrapping_pow(20), 22)),
+        b"+100000000000000000000",
+    );
+    do_test_parse(
+        parse,
+        Ok((i128::MAX, 40)),
+        b"-170141183460469231731687303715884105729",
+    );
+    do_test_parse(
+        parse,
+        Ok((u128::MAX.wrapping_mul(10).wrapping_add(9), 40)),
+        b"3402823669209384634633746074317682114559",
+    );
+    do_test_parse(
+        parse,
+        Ok((10_u64.wrapping_pow(40), 81)),
+        &[
+            b"0".repeat(40).as_slice(),
+            b"10000000000000000000000000000000000000000",
+        ]
+        .concat(),
+    );
+    do_test_parse(parse, Ok((0_u64, 0)), b"abc");
+}
+
+#[test]
+fn test_parse_canonical() {
+    do_test_parse(try_parse_canonical, Ok((0_usize, 1)), b"0");
+    do_test_parse(try_parse_canonical, Ok((0_usize, 1)), b"0,");
+    do_test_parse(try_parse_canonical, Ok((0_usize, 1)), b"0x1");
+    do_test_parse(try_parse_canonical, Ok((7_usize, 1)), b"7");
+    do_test_parse(try_parse_canonical, Ok((100_usize, 3)), b"100");
+    do_test_parse(
+        try_parse_canonical,
+        Ok((usize::MAX, 20)),
+        b"18446744073709551615",
+    );
+    do_test_parse(try_parse_canonical, Ok((0_i32, 2)), b"-0");
+    do_test_parse(try_parse_canonical, Ok((-10_i32, 3)), b"-10]");
+
+    do_test_parse(
+        try_parse_canonical::<usize>,
+        Err(ParseError::LeadingZero),
+        b"00",
+    );
+    do_test_parse(
+        try_parse_canonical::<usize>,
+        Err(ParseError::LeadingZero),
+        b"007",
+    );
+    do_test_parse(
+        try_parse_canonical::<usize>,
+        Err(ParseError::LeadingZero),
+        b"00002222221343435542",
+    );
+    do_test_parse(
+        try_parse_canonical::<usize>,
+        Err(ParseError::LeadingZero),
+        b"000000000000000000000000000000000018446744073709551615",
+    );
+    do_test_parse(
+        try_parse_canonical::<i32>,
+        Err(ParseError::LeadingZero),
+        b"-01",
+    );
+    do_test_parse(
+        try_parse_canonical::<i32>,
+        Err(ParseError::LeadingZero),
+        b"+00",
+    );
+    do_test_parse(try_parse_canonical::<usize>, Err(ParseError::Empty), b"");
+    do_test_parse(try_parse_canonical::<usize>, Err(ParseError::Empty), b"a0");
+    do_test_parse(
+        try_parse_canonical::<u8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"256",
+    );
+}
+
+#[test]
+fn test_parse_nonzero() {
+    // the `Option` variant must agree with the `Result` one
+    fn parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T::NonZero>, ParseError> {
+        let result = try_parse_nonzero::<T>(x);
+        assert_eq!(simd_parse_int::parse_nonzero::<T>(x), result.ok());
+        result
+    }
+
+    do_test_parse(parse::<u64>, Ok((NonZero::new(1).unwrap(), 1)), b"1");
+    do_test_parse(parse::<u64>, Ok((NonZero::new(1).unwrap(), 4)), b"0001");
+    do_test_parse(
+        parse::<u32>,
+        Ok((NonZero::new(1).unwrap(), 33)),
+        b"000000000000000000000000000000001,",
+    );
+    do_test_parse(
+        parse::<u64>,
+        Ok((NonZero::new(u64::MAX).unwrap(), 20)),
+        b"18446744073709551615",
+    );
+    do_test_parse(parse::<i8>, Ok((NonZero::new(-7).unwrap(), 3)), b"-07");
+    do_test_parse(
+        parse::<i128>,
+        Ok((NonZero::new(i128::MIN).unwrap(), 40)),
+        b"-170141183460469231731687303715884105728",
+    );
+
+    do_test_parse(parse::<u64>, Err(ParseError::Zero), b"0");
+    do_test_parse(parse::<u64>, Err(ParseError::Zero), b"0000");
+    do_test_parse(
+        parse::<u32>,
+        Err(ParseError::Zero),
+        b"000000000000000000000000000000000,",
+    );
+    do_test_parse(parse::<i32>, Err(ParseError::Zero), b"-0");
+    do_test_parse(parse::<i32>, Err(ParseError::Zero), b"+000");
+    do_test_parse(parse::<u64>, Err(ParseError::Empty), b"");
+    do_test_parse(parse::<u64>, Err(ParseError::Empty), b"id");
+    do_test_parse(parse::<u8>, Err(ParseError::Overflow { len: 3 }), b"256");
+
+    let id: Option<NonZero<u64>> = simd_parse_int::parse::<u64>(b"42").into();
+    assert_eq!(id, NonZero::new(42));
+    let id: Option<NonZero<u64>> = simd_parse_int::parse::<u64>(b"0000").into();
+    assert_eq!(id, None);
+    let id: Option<NonZero<u64>> = simd_parse_int::parse::<u64>(b"id").into();
+    assert_eq!(id, None);
+
+    let result: ParseResult<i16> = simd_parse_int::parse_nonzero::<i16>(b"-12").unwrap().into();
+    assert_eq!(result, ParseResult { value: -12, len: 3 });
+}
+
+#[test]
+fn test_parse_bounded() {
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535),
+        Ok((65535_u16, 5)),
+        b"65535",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535),
+        Ok((8080_u32, 4)),
+        b"8080/index.html",
+    );
+    do_test_parse(|x| try_parse_bounded(x, 12), Ok((12_u8, 2)), b"12");
+    do_test_parse(|x| try_parse_bounded(x, 12), Ok((12_u8, 9)), b"000000012");
+    do_test_parse(
+        |x| try_parse_bounded(x, 100),
+        Ok((100_u32, 30)),
+        b"000000000000000000000000000100%",
+    );
+    do_test_parse(|x| try_parse_bounded(x, 0), Ok((0_u32, 1)), b"0");
+    do_test_parse(|x| try_parse_bounded(x, 100), Ok((-5_i32, 2)), b"-5");
+    do_test_parse(
+        |x| try_parse_bounded(x, 100),
+        Ok((-2147483648_i32, 11)),
+        b"-2147483648",
+    );
+    do_test_parse(|x| try_parse_bounded(x, -10), Ok((-20_i32, 3)), b"-20");
+
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535_u32),
+        Err(ParseError::OutOfRange { len: 5 }),
+        b"65536",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535_u32),
+        Err(ParseError::OutOfRange { len: 6 }),
+        b"100000",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535_u32),
+        Err(ParseError::OutOfRange { len: 13 }),
+        b"0000000065536,",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 12_u8),
+        Err(ParseError::OutOfRange { len: 2 }),
+        b"13",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 12_u8),
+        Err(ParseError::OutOfRange { len: 3 }),
+        b"256",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 0_u8),
+        Err(ParseError::OutOfRange { len: 1 }),
+        b"1",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 100_i32),
+        Err(ParseError::OutOfRange { len: 3 }),
+        b"101 %",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 100_i32),
+        Err(ParseError::OutOfRange { len: 4 }),
+        b"+101",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, -10_i32),
+        Err(ParseError::OutOfRange { len: 2 }),
+        b"-5",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, -10_i32),
+        Err(ParseError::OutOfRange { len: 1 }),
+        b"0",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 65535_u64),
+        Err(ParseError::OutOfRange { len: 44 }),
+        b"99999999999999999999999999999999999999999999",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 100_i32),
+        Err(ParseError::Overflow { len: 12 }),
+        b"-99999999999",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 100_u32),
+        Err(ParseError::Empty),
+        b"",
+    );
+    do_test_parse(
+        |x| try_parse_bounded(x, 100_u32),
+        Err(ParseError::Empty),
+        b"port",
+    );
+
+    // every number of digits and leading zeros, starting at every alignment
+    let max = 4_000_000_u64;
+    for digits in 1..=10 {
+        for zeros in [0, 1, 5, 15, 16, 40] {
+            let mut buffer = vec![b'0'; 16 + zeros];
+            buffer.extend_from_slice(&b"4000000123"[..digits]);
+            buffer.push(b',');
+
+            let value = b"4000000123"[..digits]
+                .iter()
+                .fold(0, |value, digit| value * 10 + (digit - b'0') as u64);
+
+            for start in sampled(0..16) {
+                let input = &buffer[start..];
+                let expected = if value > max {
+                    Err(ParseError::OutOfRange {
+                        len: input.len() - 1,
+                    })
+                } else {
+                    Ok(ParseResult {
+                        value,
+                        len: input.len() - 1,
+                    })
+                };
+
+                for input in [input, &input[..input.len() - 1]] {
+                    assert_eq!(simd_parse_int::try_parse_bounded(input, max), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_with_separator() {
+    fn parse<T: ParseInt, const SEPARATOR: u8>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        try_parse_with_separator(x, SEPARATOR)
+    }
+
+    do_test_parse(parse::<_, b'_'>, Ok((1000000_u64, 9)), b"1_000_000");
+    do_test_parse(parse::<_, b','>, Ok((1000000_u64, 9)), b"1,000,000");
+    do_test_parse(parse::<_, b'\''>, Ok((1000000_u64, 9)), b"1'000'000");
+    do_test_parse(parse::<_, b' '>, Ok((1000000_u64, 9)), b"1 000 000 km");
+    do_test_parse(parse::<_, b'_'>, Ok((123456_u64, 8)), b"12_34_56");
+    do_test_parse(parse::<_, b'_'>, Ok((1234_u64, 4)), b"1234");
+    do_test_parse(parse::<_, b'_'>, Ok((1_u64, 1)), b"1__0");
+    do_test_parse(parse::<_, b'_'>, Ok((1_u64, 1)), b"1_");
+    do_test_parse(parse::<_, b'_'>, Ok((1_u64, 1)), b"1_a");
+    do_test_parse(parse::<u64, b'_'>, Err(ParseError::Empty), b"_1");
+    do_test_parse(parse::<_, b'_'>, Ok((-1000_i32, 6)), b"-1_000");
+    do_test_parse(parse::<i32, b'_'>, Err(ParseError::Empty), b"-_1");
+    do_test_parse(
+        parse::<_, b','>,
+        Ok((u64::MAX, 26)),
+        b"18,446,744,073,709,551,615",
+    );
+    do_test_parse(
+        parse::<u64, b','>,
+        Err(ParseError::Overflow { len: 26 }),
+        b"18,446,744,073,709,551,616",
+    );
+    do_test_parse(
+        parse::<_, b'_'>,
+        Ok((u128::MAX, 51)),
+        b"340_282_366_920_938_463_463_374_607_431_768_211_455",
+    );
+    do_test_parse(
+        parse::<_, b'_'>,
+        Ok((1234567890123456_u64, 46)),
+        b"0_0_0_0_0_0_0_0_0_0_0_0_0_0_0_1234567890123456,",
+    );
+
+    // a separator at every position of numbers of every length, starting at every alignment
+    let mut buffer = [b'1'; 80];
+    for start in sampled(0..16) {
+        for len in sampled(3..40) {
+            // all the digits are ones
+            let expected = "1".repeat(len - 1).parse::<u128>().unwrap();
+
+            for position in sampled(start + 1..start + len - 1) {
+                buffer[position] = b'_';
+                do_test_parse(
+                    parse::<_, b'_'>,
+                    Ok((expected, len)),
+                    &buffer[start..start + len],
+                );
+                buffer[position] = b'1';
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_with_thousands_separator() {
+    fn parse<T: ParseInt>(x: &[u8]) -> Result<ParseResult<T>, ParseError> {
+        try_parse_with_thousands_separator(x, b',')
+    }
+
+    do_test_parse(parse, Ok((1000000_u64, 9)), b"1,000,000");
+    do_test_parse(parse, Ok((100000_u64, 7)), b"100,000");
+    do_test_parse(parse, Ok((1000000_u64, 7)), b"1000000");
+    do_test_parse(parse, Ok((1_u64, 1)), b"1");
+    do_test_parse(parse, Ok((1000_u64, 5)), b"1,000,");
+    do_test_parse(parse, Ok((-1000_i32, 6)), b"-1,000");
+    do_test_parse(parse, Ok((u64::MAX, 26)), b"18,446,744,073,709,551,615");
+
+    do_test_parse(parse::<u64>, Err(ParseError::InvalidGrouping), b"10,00,000");
+    do_test_parse(parse::<u64>, Err(ParseError::InvalidGrouping), b"1,0000");
+    do_test_parse(parse::<u64>, Err(ParseError::InvalidGrouping), b"1,00");
+    do_test_parse(parse::<u64>, Err(ParseError::InvalidGrouping), b"1000,000");
+    do_test_parse(
+        parse::<u64>,
+        Err(ParseError::InvalidGrouping),
+        b"1,000,000,000,000,0000",
+    );
+    do_test_parse(parse::<u64>, Err(ParseError::Empty), b",000");
+}
+
+#[test]
+fn test_parse_delimited() {
+    do_test_delimited::<u64>(Ok(()), &[12, 4553, 8, 99901], b"12,4553,8,99901", b',');
+    do_test_delimited::<u64>(Ok(()), &[0], b"0", b',');
+    do_test_delimited::<u64>(Ok(()), &[1, 2, 3], b"1\t2\t3", b'\t');
+    do_test_delimited::<i32>(Ok(()), &[-1, 2, -300], b"-1,+2,-300", b',');
+    do_test_delimited::<i32>(Ok(()), &[1, 2], b"1-2", b'-');
+    do_test_delimited::<u64>(
+        Ok(()),
+        &[u64::MAX, 1, 7],
+        b"18446744073709551615,000000000000000000000000001,7",
+        b',',
+    );
+
+    do_test_delimited::<u64>(Err(ParseError::Empty), &[], b"", b',');
+    do_test_delimited::<u64>(Err(ParseError::Empty), &[1], b"1,,2", b',');
+    do_test_delimited::<u64>(Err(ParseError::Empty), &[1, 2], b"1,2,", b',');
+    do_test_delimited::<u64>(Err(ParseError::Empty), &[], b",1", b',');
+    do_test_delimited::<i32>(Err(ParseError::Empty), &[1], b"1,-,2", b',');
+    do_test_delimited::<u64>(Err(ParseError::InvalidDigit), &[1], b"1,2a,3", b',');
+    do_test_delimited::<u64>(Err(ParseError::InvalidDigit), &[1], b"1, 2", b',');
+    do_test_delimited::<u32>(Err(ParseError::InvalidDigit), &[], b"-1,2", b',');
+    do_test_delimited::<u8>(Err(ParseError::Overflow { len: 3 }), &[1], b"1,256", b',');
+    do_test_delimited::<u8>(Err(ParseError::InvalidDigit), &[1], b"1,300x", b',');
+    do_test_delimited::<i8>(Err(ParseError::Overflow { len: 4 }), &[1], b"1,-129", b',');
+    do_test_delimited::<u64>(
+        Err(ParseError::Overflow { len: 20 }),
+        &[1],
+        b"1,18446744073709551616",
+        b',',
+    );
+
+    // fields of every kind, inside of a chunk or across chunks, starting at every alignment
+    let fields: [&[u8]; 16] = [
+        b"0",
+        b"7",
+        b"42",
+        b"-5",
+        b"+9",
+        b"123456",
+        b"1234567891234567",
+        b"-9223372036854775808",
+        b"18446744073709551615",
+        b"18446744073709551616",
+        b"0000000000000000000000000000001",
+        b"",
+        b"a",
+        b"1a",
+        b"-",
+        b"256",
+    ];
+
+    for first in sampled(0..fields.len()) {
+        for second in sampled(0..fields.len()) {
+            for count in 1..12 {
+                let mut buffer = vec![b','; 16];
+
+                for i in 0..count {
+                    let field = [fields[(first + i) % fields.len()], fields[second]][i % 2];
+                    buffer.extend_from_slice(field);
+                    buffer.push(b',');
+                }
+
+                buffer.pop();
+
+                for start in sampled(0..16) {
+                    do_test_delimited_fields::<u8>(&buffer[start..]);
+                    do_test_delimited_fields::<i32>(&buffer[start..]);
+                    do_test_delimited_fields::<u64>(&buffer[start..]);
+                    do_test_delimited_fields::<i64>(&buffer[start..]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "delimiter can't be a digit")]
+fn test_parse_delimited_digit() {
+    let _ = simd_parse_int::parse_delimited::<u64>(b"10203", b'0', &mut Vec::new());
+}
+
+#[test]
+#[should_panic(expected = "separator can't be a digit")]
+fn test_parse_with_separator_invalid() {
+    simd_parse_int::parse_with_separator::<u64>(b"1", b'0');
+}
+
+#[test]
+fn test_parse_options() {
+    let options = ParseOptions::<i32>::new();
+    do_test_parse(|x| options.try_parse(x), Ok((-123, 4)), b"-123abc");
+    do_test_parse(|x| options.try_parse(x), Ok((7, 3)), b"007");
+    do_test_parse(|x| options.try_parse(x), Err(ParseError::Empty), b"abc");
+
+    let options = ParseOptions::<i32>::new().without_sign();
+    do_test_parse(|x| options.try_parse(x), Ok((123, 3)), b"123");
+    do_test_parse(|x| options.try_parse(x), Err(ParseError::Empty), b"-123");
+    do_test_parse(|x| options.try_parse(x), Err(ParseError::Empty), b"+123");
+
+    let options = ParseOptions::<u64>::new().without_leading_zeros();
+    do_test_parse(|x| options.try_parse(x), Ok((0, 1)), b"0");
+    do_test_parse(|x| options.try_parse(x), Ok((100, 3)), b"100");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"0100",
+    );
+
+    let options = ParseOptions::<u64>::new().exact();
+    do_test_parse(|x| options.try_parse(x), Ok((123, 3)), b"123");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::InvalidDigit),
+        b"123 ",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Ok((usize::MAX as u64, 54)),
+        b"000000000000000000000000000000000018446744073709551615",
+    );
+
+    let options = ParseOptions::<u64>::new().max(1000);
+    do_test_parse(|x| options.try_parse(x), Ok((1000, 4)), b"1000");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::OutOfRange { len: 4 }),
+        b"1001",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::OutOfRange { len: 20 }),
+        b"18446744073709551615",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::Overflow { len: 20 }),
+        b"18446744073709551616",
+    );
+
+    let options = ParseOptions::<i8>::new().max(10);
+    do_test_parse(|x| options.try_parse(x), Ok((-128, 4)), b"-128");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::OutOfRange { len: 2 }),
+        b"11",
+    );
+
+    let options = ParseOptions::<u32>::new().radix::<16>();
+    do_test_parse(|x| options.try_parse(x), Ok((0xff, 2)), b"ffg");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::Overflow { len: 9 }),
+        b"100000000",
+    );
+
+    let options = ParseOptions::<u32>::new().radix::<36>().exact();
+    do_test_parse(|x| options.try_parse(x), Ok((35, 1)), b"z");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::InvalidDigit),
+        b"z!",
+    );
+
+    let options = ParseOptions::<u32>::new()
+        .without_leading_zeros()
+        .radix::<16>();
+    do_test_parse(|x| options.try_parse(x), Ok((0, 1)), b"0");
+    do_test_parse(|x| options.try_parse(x), Ok((0, 1)), b"0g");
+    do_test_parse(|x| options.try_parse(x), Ok((0xa0, 2)), b"a0");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"0a",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"0f00",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"00",
+    );
+
+    let options = ParseOptions::<i64>::new()
+        .without_leading_zeros()
+        .radix::<36>();
+    do_test_parse(|x| options.try_parse(x), Ok((0, 1)), b"0!");
+    do_test_parse(|x| options.try_parse(x), Ok((-36, 3)), b"-10");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"0z",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"-0Z",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"00",
+    );
+
+    let options = ParseOptions::<i64>::new()
+        .without_sign()
+        .without_leading_zeros()
+        .exact()
+        .radix::<2>()
+        .max(0b1111);
+    do_test_parse(|x| options.try_parse(x), Ok((0b1010, 4)), b"1010");
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::InvalidDigit),
+        b"1012",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::LeadingZero),
+        b"01",
+    );
+    do_test_parse(
+        |x| options.try_parse(x),
+        Err(ParseError::OutOfRange { len: 5 }),
+        b"10000",
+    );
+    do_test_parse(|x| options.try_parse(x), Err(ParseError::Empty), b"-1");
+}
+
+#[test]
+fn test_overflow_len() {
+    // every length of the digit run, starting at every alignment
+    for len in sampled(21..=100) {
+        let mut buffer = vec![b'0'; 16];
+        buffer.extend_from_slice(&vec![b'9'; len]);
+        buffer.extend_from_slice(b",1");
+
+        for start in sampled(0..16) {
+            let input = &buffer[start..];
+            let number = &input[..input.len() - 2];
+            let expected = Err(ParseError::Overflow { len: number.len() });
+
+            for input in [input, number] {
+                assert_eq!(simd_parse_int::try_parse::<u64>(input), expected);
+                assert_eq!(simd_parse_int::try_parse_hex::<u64>(input), expected);
+                assert_eq!(simd_parse_int::try_parse_radix::<u64>(input, 12), expected);
+            }
+        }
+    }
+
+    do_test_parse(
+        try_parse::<u8>,
+        Err(ParseError::Overflow { len: 7 }),
+        b"1000000 1",
+    );
+    do_test_parse(
+        try_parse::<i8>,
+        Err(ParseError::Overflow { len: 22 }),
+        b"-100000000000000000000!",
+    );
+    do_test_parse(
+        try_parse_prefixed::<u32>,
+        Err(ParseError::Overflow { len: 24 }),
+        b"0x1111111111111111111111",
+    );
+    do_test_parse(
+        try_parse_prefixed::<i32>,
+        Err(ParseError::Overflow { len: 43 }),
+        b"-0b1111111111111111111111111111111111111111",
+    );
+
+    assert_eq!(
+        simd_parse_int::try_parse_with_separator::<u64>(b"1_000_000_000_000_000_000_000,1", b'_'),
+        Err(ParseError::Overflow { len: 29 })
+    );
+}
+
+#[test]
+fn test_parse_hex() {
+    do_test_parse(try_parse_hex, Ok((0_u64, 1)), b"0");
+    do_test_parse(try_parse_hex, Ok((0xa_u64, 1)), b"a");
+    do_test_parse(try_parse_hex, Ok((0xf_u64, 1)), b"F");
+    do_test_parse(try_parse_hex, Ok((0x12_u64, 2)), b"12");
+    do_test_parse(try_parse_hex, Ok((0xabcdef_u64, 6)), b"abcdef");
+    do_test_parse(try_parse_hex, Ok((0xabcdef_u64, 6)), b"ABCDEF");
+    do_test_parse(try_parse_hex, Ok((0x1234567_u64, 7)), b"1234567");
+    do_test_parse(try_parse_hex, Ok((0x9abcdef0_u64, 8)), b"9aBcDeF0gg11");
+    do_test_parse(
+        try_parse_hex,
+        Ok((0x123456789abcdef_u64, 15)),
+        b"123456789abcdef",
+    );
+    do_test_parse(
+        try_parse_hex,
+        Ok((0x123456789abcdef0_u64, 16)),
+        b"123456789abcdef0",
+    );
+    do_test_parse(
+        try_parse_hex,
+        Ok((0x123456789abcdef0_u64, 16)),
+        b"123456789abcdef0ghij",
+    );
+    do_test_parse(try_parse_hex, Ok((u64::MAX, 16)), b"ffffffffffffffff");
+    do_test_parse(try_parse_hex, Ok((u64::MAX, 16)), b"FFFFFFFFFFFFFFFF:11");
+    do_test_parse(
+        try_parse_hex,
+        Ok((u64::MAX, 40)),
+        b"000000000000000000000000ffffffffffffffff",
+    );
+    do_test_parse(
+        try_parse_hex::<u64>,
+        Err(ParseError::Overflow { len: 17 }),
+        b"10000000000000000",
+    );
+    do_test_parse(try_parse_hex::<u64>, Err(ParseError::Empty), b"g");
+    do_test_parse(try_parse_hex, Ok((0_u64, 1)), b"0x1f");
+    do_test_parse(try_parse_hex, Ok((0xff_u8, 2)), b"ff");
+    do_test_parse(
+        try_parse_hex::<u8>,
+        Err(ParseError::Overflow { len: 3 }),
+        b"100",
+    );
+    do_test_parse(try_parse_hex, Ok((-0x80_i8, 3)), b"-80");
+    do_test_parse(
+        try_parse_hex::<i8>,
+        Err(ParseError::Overflow { len: 2 }),
+        b"80",
+    );
+    do_test_parse(
+        try_parse_hex,
+        Ok((u128::MAX, 32)),
+        b"ffffffffffffffffffffffffffffffff",
+    );
+    do_test_parse(
+        try_parse_hex::<u128>,
+        Err(ParseError::Overflow { len: 33 }),
+        b"100000000000000000000000000000000",
+    );
+}
+
+#[test]
+fn test_parse_radix() {
+    let inputs: [&[u8]; 12] = [
+        b"0",
+        b"1",
+        b"10",
+        b"777",
+        b"zZ",
+        b"101010101010101010101!",
+        b"1234567812345678123456781234567812345678",
+        b"0000000000000000000000000000000000001234567",
+        b"1111111111111111111111111111111111111111111111111111111111111111",
+        b"11111111111111111111111111111111111111111111111111111111111111111",
+        b"3w5e11264sgsf",
+        b"-1",
+    ];
+
+    for radix in 2..=36 {
+        for input in inputs {
+            let len = input
+                .iter()
+                .take_while(|c| (**c as char).is_digit(radix))
+                .count();
+
+            let expected = match u64::from_str_radix(str::from_utf8(&input[..len]).unwrap(), radix)
+            {
+                Ok(value) => ParseResult { value, len },
+                Err(_) => ParseResult { value: 0, len: 0 },
+            };
+
+            assert_eq!(simd_parse_int::parse_radix::<u64>(input, radix), expected);
+        }
+    }
+
+    assert_eq!(
+        simd_parse_int::parse_radix::<i64>(
+            b"-1000000000000000000000000000000000000000000000000000000000000000",
+            2
+        ),
         ParseResult {
-            value: expected_value,
-            len: expected_len
+            value: i64::MIN,
+            len: 65
         }
     );
 }
+
+#[test]
+#[should_panic(expected = "radix must be between 2 and 36")]
+fn test_parse_radix_invalid() {
+    simd_parse_int::parse_radix::<u64>(b"1", 37);
+}
+
+#[test]
+fn test_parse_prefixed() {
+    do_test_parse(try_parse_prefixed, Ok((0x1f_u32, 4)), b"0x1F");
+    do_test_parse(try_parse_prefixed, Ok((0x1f_u32, 4)), b"0X1f,");
+    do_test_parse(try_parse_prefixed, Ok((0o755_u32, 5)), b"0o755");
+    do_test_parse(try_parse_prefixed, Ok((0o7_u32, 3)), b"0O78");
+    do_test_parse(try_parse_prefixed, Ok((0b1010_u32, 6)), b"0b1010");
+    do_test_parse(try_parse_prefixed, Ok((0b1_u32, 3)), b"0B12");
+    do_test_parse(try_parse_prefixed, Ok((1234_u32, 4)), b"1234");
+    do_test_parse(try_parse_prefixed, Ok((0_u32, 1)), b"0");
+    do_test_parse(try_parse_prefixed, Ok((0_u32, 1)), b"0y");
+    do_test_parse(try_parse_prefixed, Ok((0_u32, 2)), b"00x1");
+    do_test_parse(
+        try_parse_prefixed,
+        Ok((u64::MAX, 18)),
+        b"0xffffffffffffffff",
+    );
+    do_test_parse(
+        try_parse_prefixed,
+        Ok((u64::MAX, 66)),
+        &[b"0b".as_slice(), &[b'1'; 64]].concat(),
+    );
+    do_test_parse(
+        try_parse_prefixed,
+        Ok((0x1234_u64, 43)),
+        b"0x00000000000000000000000000000000000001234",
+    );
+
+    do_test_parse(try_parse_prefixed, Ok((-0x1f_i32, 5)), b"-0x1f");
+    do_test_parse(try_parse_prefixed, Ok((0o17_i32, 5)), b"+0o17");
+    do_test_parse(try_parse_prefixed, Ok((i8::MIN, 5)), b"-0x80");
+
+    do_test_parse(try_parse_prefixed::<u32>, Err(ParseError::Empty), b"0x");
+    do_test_parse(try_parse_prefixed::<u32>, Err(ParseError::Empty), b"0xg");
+    do_test_parse(try_parse_prefixed::<u32>, Err(ParseError::Empty), b"0o8");
+    do_test_parse(try_parse_prefixed::<u32>, Err(ParseError::Empty), b"0b2");
+    do_test_parse(try_parse_prefixed::<i32>, Err(ParseError::Empty), b"-0b");
+    do_test_parse(try_parse_prefixed::<u32>, Err(ParseError::Empty), b"x1");
+    do_test_parse(
+        try_parse_prefixed::<u8>,
+        Err(ParseError::Overflow { len: 5 }),
+        b"0x100",
+    );
+    do_test_parse(
+        try_parse_prefixed::<i8>,
+        Err(ParseError::Overflow { len: 4 }),
+        b"0x80",
+    );
+}
+
+/// Miri is too slow for the exhaustive loops, so under Miri they only run one
+/// iteration out of seven
+fn sampled<I: Iterator>(iter: I) -> StepBy<I> {
+    iter.step_by(if cfg!(miri) { 7 } else { 1 })
+}
+
+fn do_test(expected_value: usize, expected_len: usize, input: &[u8]) {
+    do_test_parse(
+        |x| Ok(simd_parse_int::parse(x)),
+        Ok((expected_value, expected_len)),
+        input,
+    );
+}
+
+/// checks `parse(input)` against the expected value and length, or the expected error
+#[track_caller]
+fn do_test_parse<I: ?Sized, T: Debug + PartialEq>(
+    parse: impl Fn(&I) -> Result<ParseResult<T>, ParseError>,
+    expected: Result<(T, usize), ParseError>,
+    input: &I,
+) {
+    let expected = expected.map(|(value, len)| ParseResult { value, len });
+
+    assert_eq!(parse(input), expected);
+}
+
+/// compares parse_fixed to parse_exact for numbers of length N, with a sign or
+/// a non-digit at every position
+fn do_test_fixed_lengths<const N: usize>() {
+    fn check<T: ParseInt + Debug, const N: usize>(input: &[u8; N]) {
+        let expected = simd_parse_int::try_parse_exact::<T>(input).map_err(|error| match error {
+            ParseError::Overflow { .. } if input[1..].iter().any(|c| !c.is_ascii_digit()) => {
+                ParseError::InvalidDigit
+            }
+            error => error,
+        });
+
+        assert_eq!(simd_parse_int::try_parse_fixed::<T, N>(input), expected);
+    }
+
+    for digit in *b"0159" {
+        let mut input = [digit; N];
+        input[0] = b'1';
+
+        for position in sampled(0..N) {
+            for replacement in [b'/', b':', b'-', b'+', b'a', 0, 0xff] {
+                let original = input[position];
+                input[position] = replacement;
+
+                check::<u8, N>(&input);
+                check::<i32, N>(&input);
+                check::<u64, N>(&input);
+                check::<i64, N>(&input);
+                check::<u128, N>(&input);
+
+                input[position] = original;
+            }
+        }
+
+        check::<u8, N>(&input);
+        check::<i32, N>(&input);
+        check::<u64, N>(&input);
+        check::<i64, N>(&input);
+        check::<u128, N>(&input);
+    }
+}
+
+fn do_test_delimited<T: ParseInt + Debug>(
+    expected: Result<(), ParseError>,
+    expected_values: &[T],
+    input: &[u8],
+    delimiter: u8,
+) {
+    let mut values: Vec<T> = Vec::new();
+    assert_eq!(
+        simd_parse_int::parse_delimited(input, delimiter, &mut values),
+        expected
+    );
+    assert_eq!(values, expected_values);
+}
+
+/// compares parse_delimited to try_parse_exact on every field, with non-digits taking
+/// precedence over overflows
+fn do_test_delimited_fields<T: ParseInt + Debug>(input: &[u8]) {
+    let mut expected_values = Vec::new();
+    let mut expected = Ok(());
+
+    for field in input.split(|&c| c == b',') {
+        match simd_parse_int::try_parse_exact::<T>(field) {
+            Ok(result) => expected_values.push(result.value),
+            Err(ParseError::Overflow { .. }) if field[1..].iter().any(|c| !c.is_ascii_digit()) => {
+                expected = Err(ParseError::InvalidDigit);
+                break;
+            }
+            Err(error) => {
+                expected = Err(error);
+                break;
+            }
+        }
+    }
+
+    do_test_delimited(expected, &expected_values, input, b',');
+}